@@ -0,0 +1,58 @@
+//! Where a scrollview gets the time from when a caller doesn't give one
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::Timestamp;
+
+/// A source of the current time, in ms
+///
+/// Only consulted for events pushed without a timestamp, so it must share an epoch with any
+/// timestamps that are given. Set with Scrollview::set_clock()
+pub trait Clock: Send {
+    fn now(&self) -> f64;
+}
+
+/// The real clock, measuring from the same epoch as `Instant` timestamps do. This is the default
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> f64 {
+        std::time::Instant::now().as_millis()
+    }
+}
+
+/// A clock that only moves when told to, for driving a scrollview deterministically
+///
+/// Clones share the same time, so one can be handed to set_clock() and another kept to advance
+/// it from outside, from another thread if need be
+#[derive(Clone, Debug, Default)]
+pub struct MockClock {
+    // bits of the f64 time, since there's no atomic float
+    now: Arc<AtomicU64>,
+}
+
+impl MockClock {
+    pub fn new(start: f64) -> MockClock {
+        MockClock { now: Arc::new(AtomicU64::new(start.to_bits())) }
+    }
+
+    /// Move the time forward (or back, for a negative ms) by ms
+    pub fn advance(&self, ms: f64) {
+        // retried on contention, and always given a new value, so this can't fail
+        let _ = self.now.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |bits| {
+            Some((f64::from_bits(bits) + ms).to_bits())
+        });
+    }
+
+    pub fn set(&self, ms: f64) {
+        self.now.store(ms.to_bits(), Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> f64 {
+        f64::from_bits(self.now.load(Ordering::SeqCst))
+    }
+}
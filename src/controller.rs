@@ -0,0 +1,80 @@
+//! Owns and steps many scrollviews together, for hosts with more than a handful of them
+
+use std::collections::BTreeMap;
+
+use crate::{Scrollview, Timestamp};
+
+/// Identifies a scrollview owned by a ScrollController
+///
+/// Ids are never reused, so one kept after its view was removed just finds nothing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ViewId(u64);
+
+/// A collection of scrollviews that are stepped together once per frame
+///
+/// Events are routed to a view by borrowing it through get_mut() with its id. Nothing about a
+/// view changes by being here, so one can be taken back out with remove() and used standalone
+#[derive(Default)]
+pub struct ScrollController {
+    views: BTreeMap<ViewId, Scrollview>,
+    next_id: u64,
+}
+
+impl ScrollController {
+    pub fn new() -> ScrollController {
+        ScrollController::default()
+    }
+
+    /// Take ownership of a scrollview, giving the id to reach it by from now on
+    pub fn add(&mut self, view: Scrollview) -> ViewId {
+        let id = ViewId(self.next_id);
+        self.next_id += 1;
+        self.views.insert(id, view);
+
+        id
+    }
+
+    /// Give back the scrollview with the given id, if it's still here
+    pub fn remove(&mut self, id: ViewId) -> Option<Scrollview> {
+        self.views.remove(&id)
+    }
+
+    pub fn get(&self, id: ViewId) -> Option<&Scrollview> {
+        self.views.get(&id)
+    }
+
+    /// Borrow a view to push events to or query it
+    pub fn get_mut(&mut self, id: ViewId) -> Option<&mut Scrollview> {
+        self.views.get_mut(&id)
+    }
+
+    /// Every view along with its id, in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = (ViewId, &Scrollview)> {
+        self.views.iter().map(|(&id, view)| (id, view))
+    }
+
+    pub fn len(&self) -> usize {
+        self.views.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.views.is_empty()
+    }
+
+    /// Call step_frame() on every view, giving the ids of those still animating afterward
+    ///
+    /// The host only needs to keep requesting frames while the result is non-empty
+    pub fn step_frame_all<T: Timestamp>(&mut self, timestamp: T) -> Vec<ViewId> {
+        self.views.iter_mut()
+            .filter_map(|(&id, view)| {
+                view.step_frame(timestamp);
+                view.animating().then_some(id)
+            })
+            .collect()
+    }
+
+    /// True if any view should continue to be polled
+    pub fn animating(&self) -> bool {
+        self.views.values().any(Scrollview::animating)
+    }
+}
@@ -0,0 +1,60 @@
+//! Drives a scrollview's render loop from async code
+//!
+//! Only compiled in with the `async` feature. This is a thin layer over mark_frame(),
+//! step_frame() and animating(); nothing here does anything the synchronous api can't.
+
+use std::cell::RefCell;
+use std::future::Future;
+
+use crate::{AxisVector, Scrollview, Timestamp};
+
+/// Produces a position per frame while a scrollview is animating, with frames paced by a
+/// caller-provided timer
+///
+/// The timer is called once per frame and should give a future that resolves when it's time to
+/// render the next one, with that frame's timestamp (for instance, on a vsync or a fixed
+/// interval from the runtime's own timer).
+///
+/// The scrollview is held in a RefCell and only borrowed while a frame is being stepped, so
+/// input handlers on other tasks can keep pushing events to it while the driver waits
+pub struct FrameDriver<F> {
+    timer: F,
+    // whether any frame has been produced since the view last settled
+    running: bool,
+}
+
+impl<F, Fut, T> FrameDriver<F> where F: FnMut() -> Fut, Fut: Future<Output = T>, T: Timestamp {
+    pub fn new(timer: F) -> FrameDriver<F> {
+        FrameDriver { timer, running: false }
+    }
+
+    /// Waits for frames until the view is animating, then gives the position for each frame
+    /// it animates on, including the one it settles on
+    ///
+    /// Gives None once after the view has settled, marking the end of that run of frames. The
+    /// next call after that waits for animation to start again
+    pub async fn next_frame(&mut self, view: &RefCell<Scrollview>) -> Option<AxisVector<f64>> {
+        loop {
+            let timestamp = (self.timer)().await;
+
+            let mut view = view.borrow_mut();
+            if view.animating() {
+                self.running = true;
+                return Some(view.sample(timestamp));
+            }
+
+            if self.running {
+                self.running = false;
+                return None;
+            }
+        }
+    }
+
+    /// Calls on_frame with the position for each frame until the view next settles, waiting
+    /// first for it to start animating if it isn't yet
+    pub async fn run_until_settled<C: FnMut(AxisVector<f64>)>(&mut self, view: &RefCell<Scrollview>, mut on_frame: C) {
+        while let Some(position) = self.next_frame(view).await {
+            on_frame(position);
+        }
+    }
+}
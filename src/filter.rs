@@ -0,0 +1,15 @@
+//! Caller-supplied preprocessing of pan deltas, see Scrollview::push_input_filter()
+
+use crate::Axis;
+
+/// One stage of preprocessing applied to each pan delta before it reaches the physics, such as
+/// a deadzone, low-pass or inversion
+///
+/// Filters run in the order they were pushed, each given the output of the one before. State
+/// is kept per filter, not per axis, so a filter that smooths should track each axis itself
+pub trait InputFilter: Send {
+    /// Transform a delta pushed on axis, given dt ms since the last pan on that axis (0 for the
+    /// first pan of a gesture). Deltas are always changes in position, whatever
+    /// set_pan_direction() says they were pushed as
+    fn filter(&mut self, axis: Axis, delta: f64, dt: f64) -> f64;
+}
@@ -50,6 +50,62 @@ const MULTIPLY_FIRST_EVENT: f64 = 500.0;
 
 const FIRST_EVENT_SLOPE: f64 = -0.8;*/
 
+// how far ahead a peek is simulated when working out how hard to throw the content
+const PEEK_PREDICT_STEPS: usize = 100;
+const PEEK_PREDICT_INTERVAL: TimeDelta = 8.0;
+
+// how finely, and for how long at most, a fling is simulated to find where it comes to rest
+const REST_PREDICT_INTERVAL: TimeDelta = 8.0;
+const REST_PREDICT_MAX_STEPS: usize = 2000;
+
+// with output interpolation, the weight the newest frame's velocity gets in the smoothed
+// velocity, and the share of the gap to the true position closed each frame regardless, so
+// the output never trails far behind
+const OUTPUT_VELOCITY_WEIGHT: f64 = 0.5;
+const OUTPUT_CATCHUP: f64 = 0.25;
+
+// a fling thrown further out while already overscrolled can only add this many px to the
+// overscroll, and is pulled back by a spring this many times stiffer than usual
+const OUTWARD_FLING_MAX_EXTRA_PX: f64 = 32.0;
+const OUTWARD_FLING_SPRING_BOOST: f64 = 2.0;
+
+// with flings scaled by extent, the scroll extent at which they're left as they are, and the
+// furthest they're scaled from that either way
+const FLING_REFERENCE_EXTENT_PX: f64 = 4000.0;
+const FLING_EXTENT_SCALE_RANGE: (f64, f64) = (0.25, 4.0);
+
+// how many synthetic events seed_velocity() lays down, and how far apart
+const SEED_EVENT_COUNT: usize = 3;
+const SEED_EVENT_INTERVAL: TimeDelta = 8.0;
+
+// how close, in px, a spring must be to where it's headed (too close to see the rest of the way
+// go) before it's counted toward being settled outright, and for how many frames by default
+const SETTLE_GUARD_PX: f64 = 0.5;
+const SETTLE_FRAMES: usize = 10;
+
+// how long a fling left with nowhere to go takes by default to ease back in, see collapse
+const COLLAPSE_DURATION_MS: TimeDelta = 300.0;
+
+// how long a fling changed over to a new model mid-flight blends from the old one, see
+// set_fling_model()
+const MODEL_BLEND_MS: TimeDelta = 150.0;
+
+// halvings taken in narrowing down how quickly a jerk limited spring may close in, see
+// jerk_limited_pole()
+const POLE_SEARCH_STEPS: usize = 40;
+
+// how far either side of a grid boundary, as a share of the cell size, snap magnetism reaches
+const MAGNET_RADIUS_CELLS: f64 = 0.25;
+
+/// A spring under a jerk limit, which moves as a critically damped third order system
+#[derive(Clone, Copy)]
+struct SpringMotion {
+    // where it's headed, how quickly it closes in (per ms), and its acceleration in px/ms²
+    rest: Position,
+    pole: f64,
+    acceleration: f64,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Phase {
     Inactive,
@@ -57,13 +113,16 @@ enum Phase {
     Released(Time), // the velocity and time the release was done at
 }
 
+#[derive(Clone, Copy)]
 enum TrackPosition {
     Top,
     Bottom,
 }
 
+#[derive(Clone, Copy)]
 enum BounceState {
     Bouncing(TrackPosition),
+    Settling(Position), // springing in to a grid cell boundary
     Normal,
 }
 
@@ -117,6 +176,7 @@ impl std::fmt::Display for Sample {
 }
 
 
+#[derive(Clone)]
 pub struct Interpolator {
     redistributable: bool,
     // need record of samplings, matched with timestamps or
@@ -140,7 +200,138 @@ pub struct Interpolator {
 
     last_value: f64,
     flips_same_value: u64,
+
+    // frames in a row a spring has spent within SETTLE_GUARD_PX of where it's headed and barely
+    // moving, and how many of those it takes to settle it outright
+    calm_frames: usize,
+    settle_frames: usize,
     source: crate::Source,
+
+    // whether an axis with no scrollable extent still rubber-bands on pan
+    empty_overscroll: bool,
+
+    // furthest content can be pulled past either bound, in px
+    max_overscroll: f64,
+
+    // how strongly pans resist being pulled past a bound, in place of the config's elasticity
+    // coefficient if given, and how far momentum alone can carry content past one
+    drag_overscroll_resistance: Option<f64>,
+    fling_overscroll_limit: f64,
+
+    // below accel_min_velocity input tracks 1:1, and acceleration never multiplies
+    // velocity by more than accel_max_multiplier
+    accel_min_velocity: Velocity,
+    accel_max_multiplier: f64,
+
+    // share of a diagonal movement's speed that's along this axis, so acceleration can be
+    // worked out from the speed of the whole movement. 1 unless pans move both axes at once
+    axis_share: f64,
+
+    // when off, pans aren't accelerated, and acceleration is instead applied to the velocity
+    // flings start with, on the first step after release
+    accel_affects_drag: bool,
+
+    // scales the velocity a fling starts with, on the first step after release, and whether
+    // it's scaled by the scroll extent as well
+    fling_multiplier: f64,
+    fling_scale_by_extent: bool,
+    fling_start_pending: bool,
+
+    // velocity the current fling started with, once it's taken its first step
+    fling_start_velocity: Option<Velocity>,
+
+    // velocity in px/ms measured by a caller's tracker, taken in place of the event history's
+    // at the next release
+    release_velocity: Option<Velocity>,
+
+    // furthest position a fling thrown outward from an overscroll may reach, see
+    // resist_outward_fling()
+    outward_fling_limit: Option<Position>,
+
+    // while a fling is being arrested, when that started, how long it has to stop in, and the
+    // speed it was going at the start
+    arrest: Option<(Time, TimeDelta, Velocity)>,
+
+    // while a fling whose track stopped being scrollable is easing back in, when that started
+    // and the position it started from, along with how long the ease takes
+    collapse: Option<(Time, Position)>,
+    collapse_duration: TimeDelta,
+
+    // absolute distance moved by pans and flings, in either direction
+    total_scrolled: f64,
+
+    fling_model: crate::FlingModel,
+    // the model a fling in progress was switched from, and when, while decay still blends out
+    // of it
+    model_blend: Option<(Time, crate::FlingModel)>,
+
+    // cell size and offset of the grid that flings settle onto, if any
+    grid: Option<(f64, f64)>,
+    // where flings resting in a partial last cell settle
+    end_bias: crate::EndBias,
+    // how strongly grid boundaries pull on a drag passing near them, from 0 to 1, and how far
+    // the most recent sample was pulled. The pull is only ever held while dragging, and once
+    // let go the content carries on from where it was shown
+    magnetism: f64,
+    magnet_pull: Position,
+
+    // tolerance in px for deciding the track is settled or at a bound, and in px/ms for
+    // deciding it's not moving
+    epsilon: f64,
+    velocity_epsilon: f64,
+
+    // when on, a fling that settles is placed exactly on the bound it came to rest against,
+    // with no residual velocity left over to creep
+    exact_rest: bool,
+
+    // when on, displayed_position() glides along the smoothed velocity between frames rather
+    // than following every step of sparse input. Holds the last frame's time, the position
+    // shown, the true position then, and the smoothed velocity in px/ms
+    output_interpolation: bool,
+    displayed: Option<(Time, Position, Position, Velocity)>,
+
+    // while frozen sample() doesn't advance, and the first sample() after thawing
+    // skips over however long the freeze lasted
+    frozen: bool,
+    thawing: bool,
+
+    // number of events pushed since the last interrupt, and how far ahead of the
+    // last sample's time its integration was led
+    gesture_events: usize,
+    lead: TimeDelta,
+
+    // where the most recent sample() had got to at its own time, before the lead, along with
+    // that time
+    tracked: Option<(Time, Position)>,
+
+    // full prediction lead to use in place of SHIFT_WINDOW_MS, if given
+    lead_override: Option<TimeDelta>,
+
+    compaction: crate::CompactionMode,
+
+    // events and samples older than this many ms at a step are dropped
+    max_sample_age: TimeDelta,
+
+    resample: crate::ResampleMode,
+
+    // velocity left over from a fling caught by the current gesture, to be added on to
+    // its own fling if it goes the same way
+    fling_inheritance: bool,
+    inherited_velocity: Option<Velocity>,
+
+    // velocity in px/ms above which the lead is scaled down
+    prediction_rolloff: f64,
+
+    // when off, this axis is never led ahead of the input
+    prediction_enabled: bool,
+
+    // most a spring's acceleration may change by per ms, and the spring under way within it,
+    // see jerk_limited_spring()
+    jerk_limit: f64,
+    spring_motion: Option<SpringMotion>,
+
+    // tuning that takes the place of the config file's for this axis, see config()
+    profile: Option<crate::ScrollProfile>,
     //events_y: RangedMap<Timestamp, Event>,
 
 }
@@ -171,15 +362,75 @@ impl Interpolator {
             track_bound_upper: track_bounds.1,
             track_initial_pos: initial_position,
             flips_same_value: 0,
+            calm_frames: 0,
+            settle_frames: SETTLE_FRAMES,
             last_value: 0.0,
             bouncing: BounceState::Normal,
             source: crate::Source::Undefined,
+            empty_overscroll: true,
+            max_overscroll: f64::INFINITY,
+            drag_overscroll_resistance: None,
+            fling_overscroll_limit: f64::INFINITY,
+            accel_min_velocity: 0.0,
+            accel_max_multiplier: f64::INFINITY,
+            axis_share: 1.0,
+            accel_affects_drag: true,
+            fling_multiplier: 1.0,
+            fling_scale_by_extent: false,
+            fling_start_pending: false,
+            fling_start_velocity: None,
+            release_velocity: None,
+            outward_fling_limit: None,
+            arrest: None,
+            collapse: None,
+            collapse_duration: COLLAPSE_DURATION_MS,
+            magnetism: 0.0,
+            magnet_pull: 0.0,
+            total_scrolled: 0.0,
+            fling_model: crate::FlingModel::Curve,
+            model_blend: None,
+            grid: None,
+            end_bias: crate::EndBias::Nearest,
+            gesture_events: 0,
+            lead: 0.0,
+            tracked: None,
+            lead_override: None,
+            prediction_rolloff: f64::INFINITY,
+            prediction_enabled: true,
+            jerk_limit: f64::INFINITY,
+            spring_motion: None,
+            profile: None,
+            compaction: crate::CompactionMode::Discard,
+            max_sample_age: f64::INFINITY,
+            resample: crate::ResampleMode::Linear,
+            fling_inheritance: false,
+            inherited_velocity: None,
+            frozen: false,
+            thawing: false,
+            epsilon: 0.01,
+            velocity_epsilon: 0.01,
+            exact_rest: true,
+            output_interpolation: false,
+            displayed: None,
         }
     }
 
     pub fn sample(&mut self, time: Time) -> Position {
-        let config = sconfig.read().unwrap();
+        let config = self.config();
+
+        if self.frozen {
+            return self.position();
+        } else if self.thawing {
+            // carry on from where the freeze left off, as if no time had passed
+            self.thawing = false;
+            self.retime(time);
+        }
 
+        if self.samples.back().is_some_and(|s| !s.position.is_finite() || !s.velocity.is_finite()) {
+            self.recover(time);
+        }
+
+        self.expire(time);
         self.prevent_coast(time);
         //let time = time + 33.0;
 
@@ -191,12 +442,34 @@ impl Interpolator {
         }*/
 
         //let time_range = timestamp - last_sample.timestamp;
-        let iter = iter_range(last_sample.time + config.SHIFT_WINDOW_MS, time + config.SHIFT_WINDOW_MS, config.TIMESTEP);
+        // lead ahead less while there's too little input to trust the prediction, or while
+        // moving fast enough that a full lead would visibly overshoot
+        let lead = if self.prediction_enabled {
+            self.lead_override.unwrap_or(config.SHIFT_WINDOW_MS)
+                * self.prediction_confidence()
+                * self.rolloff(last_sample.velocity)
+        } else {
+            0.0
+        };
+        let start = last_sample.time + self.lead;
+        let end = (time + lead).max(start);
+        let iter = iter_range(start, end, config.TIMESTEP);
+        // a lead that shrank by more than a frame leaves integration ahead of time + lead, so
+        // keep how far ahead it really got, or the next sample would integrate that stretch again
+        self.lead = end - time;
 
         //let mut pos_delta = 0.0;
 
         let mut cur_position = last_sample.position;
         let mut cur_velocity = last_sample.velocity;
+        // with a lead longer than the frame, the last sample had already integrated past time,
+        // so the best there is to go on is where the track was between it and the one before
+        let mut tracked = match self.tracked {
+            Some((previous, from)) if previous <= time && time < start => {
+                from + (last_sample.position - from) * ((time - previous) / (start - previous))
+            },
+            _ => last_sample.position,
+        };
         //println!("Starts {} {}", cur_position_step, cur_velocity_step);
         for (start, end) in iter {
             let stepped_velocity = self.step_velocity(start, end, cur_position, cur_velocity);
@@ -213,6 +486,43 @@ impl Interpolator {
 
             cur_position += integral;
             cur_velocity = stepped_velocity;
+
+            // the spring only ever pulls back in, so this can only bite while pushing outward
+            let (lower, upper) = (self.track_bound_lower - self.max_overscroll, self.track_bound_upper + self.max_overscroll);
+            let (lower, upper) = match self.outward_fling_limit {
+                Some(limit) if limit > self.track_bound_upper => (lower, upper.min(limit)),
+                Some(limit) => (lower.max(limit), upper),
+                None => (lower, upper),
+            };
+            if cur_position < lower || cur_position > upper {
+                cur_position = cur_position.clamp(lower, upper);
+                cur_velocity = 0.0;
+            }
+
+            // momentum may carry on out to the fling limit, though never pulls content back in
+            // to it if a drag left it further out than that
+            if let Phase::Released(_) = self.current_phase {
+                let (lower, upper) = (
+                    self.track_bound_lower - self.fling_overscroll_limit,
+                    self.track_bound_upper + self.fling_overscroll_limit,
+                );
+                if cur_position > upper && integral > 0.0 {
+                    cur_position = (cur_position - integral).max(upper);
+                    cur_velocity = 0.0;
+                } else if cur_position < lower && integral < 0.0 {
+                    cur_position = (cur_position - integral).min(lower);
+                    cur_velocity = 0.0;
+                }
+            }
+
+            // pans are counted as they come in, so only count what the fling adds
+            if let Phase::Released(_) = self.current_phase {
+                self.total_scrolled += integral.abs();
+            }
+
+            if end <= time {
+                tracked = cur_position;
+            }
             /*println!("Integrates over {}, {} with approx_vel {} to reach {}",
                      start, end, approx_vel, integral);*/
         }
@@ -231,13 +541,57 @@ impl Interpolator {
             cur_velocity = 0.0;
         }*/
 
+        // with nowhere left to scroll, the fling is replaced outright by an ease back to the
+        // only position left, so it neither stops dead overscrolled nor slams back on the spring
+        if let Some((start, from)) = self.collapse {
+            let progress = if self.collapse_duration > 0.0 {
+                ((time - start) / self.collapse_duration).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+            // eased at both ends, since the content is turning around from wherever the fling
+            // was taking it
+            let eased = progress * progress * (3.0 - 2.0 * progress);
+
+            cur_position = from + (self.track_bound_lower - from) * eased;
+            cur_velocity = 0.0;
+            tracked = cur_position;
+
+            if progress >= 1.0 {
+                self.collapse = None;
+                self.bouncing = BounceState::Normal;
+                self.spring_motion = None;
+                self.current_phase = Phase::Inactive;
+            }
+        }
+
+        // the pull is reworked from scratch on every sample of a drag, so it never compounds
+        let pull = if self.current_phase == Phase::Interpolating {
+            self.magnet_pull_at(cur_position - self.magnet_pull)
+        } else {
+            self.magnet_pull = 0.0;
+            0.0
+        };
+        cur_position += pull - self.magnet_pull;
+        tracked += pull - self.magnet_pull;
+        self.magnet_pull = pull;
+
         self.samples.push_back(Sample { time, velocity: cur_velocity, position: cur_position });
+        self.tracked = Some((time, tracked));
+
+        // an arrested fling has had all the time it was given, so it's done
+        if let Some((start, duration, _)) = self.arrest {
+            if time >= start + duration && !self.outside_bounds(cur_position) {
+                self.signal_interrupt(time);
+            }
+        }
 
         //self.check_idle(position);
 
         self.cull();
         //self.check_idle(cur_position);
         self.check_idle(cur_position, cur_velocity);
+        self.smooth_output(time, cur_position);
 
         /*if position.is_nan() {
             panic!("Was going to return NaN position");
@@ -253,30 +607,324 @@ impl Interpolator {
         cur_position
     }
 
+    /// Gives the positions this interpolator would report at each of the next `steps` intervals
+    /// if no further events arrived, without disturbing the real state
+    pub fn predict(&self, steps: usize, interval: TimeDelta) -> Vec<Position> {
+        let mut ghost = self.clone();
+        let start = self.samples.back().map(|s| s.time).unwrap_or(0.0);
+
+        (1..=steps).map(|step| ghost.sample(start + interval * step as f64)).collect()
+    }
+
+    /// Where a fling in progress will come to rest if nothing interrupts it, found by running it
+    /// to completion on a copy. Gives the current position if not flinging
+    pub fn rest_position(&self) -> Position {
+        let mut ghost = self.clone();
+        let mut time = self.samples.back().map(|s| s.time).unwrap_or(0.0);
+
+        if let Phase::Released(_) = self.current_phase {
+            for _ in 0..REST_PREDICT_MAX_STEPS {
+                if !ghost.animating() {
+                    break;
+                }
+
+                time += REST_PREDICT_INTERVAL;
+                ghost.sample(time);
+            }
+        }
+
+        ghost.position()
+    }
+
+    /// How far past its nearest bound the content would end up if the momentum it has now were
+    /// left to run out with nothing pulling it back, signed by which bound (positive past the
+    /// upper one). A drag still in progress is treated as released now
+    pub fn projected_overscroll(&self) -> Position {
+        let mut ghost = self.clone();
+        let mut time = self.samples.back().map(|s| s.time).unwrap_or(0.0);
+
+        match self.current_phase {
+            Phase::Interpolating => {
+                // extent scaling is worked out from the bounds, which are about to go
+                ghost.fling_multiplier *= ghost.extent_scale();
+                ghost.fling_scale_by_extent = false;
+                ghost.signal_fling(time);
+            },
+            Phase::Released(_) => {},
+            Phase::Inactive => return self.overscroll_at(self.position()),
+        }
+
+        // with no bounds there's nothing to bounce off, snap to, or be held back by
+        ghost.track_bound_lower = f64::NEG_INFINITY;
+        ghost.track_bound_upper = f64::INFINITY;
+        ghost.bouncing = BounceState::Normal;
+        ghost.grid = None;
+        ghost.outward_fling_limit = None;
+        ghost.collapse = None;
+
+        for _ in 0..REST_PREDICT_MAX_STEPS {
+            if !ghost.animating() {
+                break;
+            }
+
+            time += REST_PREDICT_INTERVAL;
+            ghost.sample(time);
+        }
+
+        self.overscroll_at(ghost.position())
+    }
+
+    /// Distance the track is out past its nearest bound, signed by which bound (positive past
+    /// the upper one), 0 if within them
+    pub fn overscroll(&self) -> Position {
+        self.overscroll_at(self.displayed_position())
+    }
+
+    /// The position the most recent sample() had reached at its own time, before integrating on
+    /// ahead by the prediction lead
+    pub fn tracked_position(&self) -> Position {
+        // at rest there's nothing to lead by
+        match (self.tracked, self.samples.back()) {
+            (Some((time, position)), Some(last)) if time == last.time && self.animating() => position,
+            _ => self.position(),
+        }
+    }
+
+    /// Distance from position out past the nearest bound, 0 if within them
+    fn overscroll_at(&self, position: Position) -> Position {
+        if position > self.track_bound_upper {
+            position - self.track_bound_upper
+        } else if position < self.track_bound_lower {
+            position - self.track_bound_lower
+        } else {
+            0.0
+        }
+    }
+
+    /// Throws the content a given distance out past its nearest bound, for bounce() to spring
+    /// back in, as an affordance hinting that the track scrolls
+    pub fn peek(&mut self, time: Time, distance: f64) {
+        let position = self.position();
+        let (bound, outward) = if self.track_bound_upper - position < position - self.track_bound_lower {
+            (self.track_bound_upper, 1.0)
+        } else {
+            (self.track_bound_lower, -1.0)
+        };
+
+        let config = self.config();
+        // rough velocity for an undamped spring to reach distance, refined below against the
+        // real damped spring by trying it out on a copy
+        let omega = (2.0 * config.OVERSCROLL_SPRING_CONSTANT / (config.TIMESTEP * config.CONTENT_MASS_VALUE)).sqrt();
+        let mut velocity = distance.abs() * omega * config.TIMESTEP / 2.0;
+
+        for _ in 0..3 {
+            let mut ghost = self.clone();
+            ghost.kick(time, bound, velocity * outward);
+
+            let peak = ghost.predict(PEEK_PREDICT_STEPS, PEEK_PREDICT_INTERVAL)
+                .into_iter()
+                .map(|p| (p - bound).abs())
+                .fold(0.0, f64::max);
+
+            if peak <= 0.0 {
+                break;
+            }
+            velocity *= distance.abs() / peak;
+        }
+
+        self.kick(time, bound, velocity * outward);
+    }
+
+    fn kick(&mut self, time: Time, from: Position, velocity: Velocity) {
+        // released "forever ago" so the next step goes straight to bounce() and decay()
+        self.current_phase = Phase::Released(f64::NEG_INFINITY);
+        self.bouncing = BounceState::Normal;
+        self.spring_motion = None;
+        self.arrest = None;
+        self.flips_same_value = 0;
+        self.samples.push_back(Sample { time, velocity, position: from });
+    }
+
     pub fn cull(&mut self) {
-        let config = sconfig.read().unwrap();
+        let config = self.config();
 
         while self.samples.len() > config.SAMPLE_EXPIRY_COUNT {
             self.samples.pop_front();
         }
-        while self.events.len() > config.EVENT_EXPIRY_COUNT {
-            self.events.pop_front();
+        match self.compaction {
+            crate::CompactionMode::Discard => {
+                while self.events.len() > config.EVENT_EXPIRY_COUNT {
+                    self.events.pop_front();
+                }
+            },
+            crate::CompactionMode::Downsample => {
+                if self.events.len() > config.EVENT_EXPIRY_COUNT {
+                    // drop every other event from the older half, so history thins out with age
+                    // rather than being cut off. The newest events, which velocity is interpolated
+                    // from, are left as they are
+                    let older_half = self.events.len() / 2;
+                    let mut index = 0;
+                    self.events.retain(|_| {
+                        index += 1;
+                        index > older_half || index % 2 == 1
+                    });
+                }
+            },
         }
     }
 
     pub fn signal_fling(&mut self, time: Time) {
         //println!("Fling at {}", time);
-        self.current_phase = Phase::Released(time);
+        // a fling takes its velocity from the drag in the step the release falls in, so with
+        // integration led on past time, release from the next step it has yet to reach
+        let integrated_to = self.samples.back().map_or(time, |last| last.time + self.lead);
+        self.current_phase = Phase::Released(time.max(integrated_to));
+        self.fling_start_pending = true;
+        self.fling_start_velocity = None;
+        self.outward_fling_limit = None;
+        self.arrest = None;
 
         //self.flush(time);
     }
 
+    /// Has the next release fling at velocity (px/ms), rather than what the event history says
+    pub fn set_release_velocity(&mut self, velocity: Velocity) {
+        self.release_velocity = Some(velocity);
+    }
+
+    /// Ends a drag, flinging if it was still moving at min_velocity or faster when released,
+    /// and otherwise coming to rest where it is (after springing in, if overscrolled or off the
+    /// snap grid). Gives whether it flung
+    pub fn signal_release(&mut self, time: Time, min_velocity: Velocity) -> bool {
+        // already let go, and nothing since to release again
+        if let Phase::Released(_) = self.current_phase {
+            self.release_velocity = None;
+            return false;
+        }
+
+        let config = self.config();
+
+        // held still before lifting, however fast it moved earlier
+        let held = self.events.back().is_some_and(|last| time - last.time > config.MAX_MS_WITHOUT_ZERO_INJECTION);
+        if held {
+            self.release_velocity = None;
+        }
+
+        let velocity = match (self.release_velocity, self.gesture_events, self.events.len()) {
+            _ if held => 0.0,
+            (Some(velocity), _, _) => velocity,
+            (None, 2.., len) if len >= 2 => Self::slope_of(&self.events[len - 1], &self.events[len - 2]),
+            _ => 0.0,
+        };
+
+        if velocity.abs() >= min_velocity {
+            self.signal_fling(time);
+            true
+        } else {
+            self.settle(time);
+            false
+        }
+    }
+
+    /// Stops the track, then springs it from rest in to the nearest bound or snap point if it
+    /// isn't on one, so no velocity left over from earlier in the gesture carries into it
+    fn settle(&mut self, time: Time) {
+        let position = self.position();
+        let placed = !self.outside_bounds(position)
+            && self.snap_target(position).is_none_or(|target| (target - position).abs() <= self.epsilon);
+
+        self.signal_interrupt(time);
+
+        if !placed {
+            self.kick(time, position, 0.0);
+        }
+    }
+
     pub fn signal_interrupt(&mut self, time: Time) {
+        // redundant interrupt, nothing has happened since the last one that needs stopping
+        if self.current_phase == Phase::Inactive && self.gesture_events == 0 {
+            return;
+        }
+
         println!("Interrupt at {}", time);
         //panic!("Interrupt not impl");
+
+        // catching a fling that's still going, hold on to what's left of it in case the new
+        // gesture flings on in the same direction
+        self.inherited_velocity = match (self.current_phase, self.samples.back()) {
+            (Phase::Released(_), Some(last)) if self.fling_inheritance && !self.negligible_velocity(last.velocity) => {
+                Some(last.velocity)
+            },
+            _ => None,
+        };
+
         self.current_phase = Phase::Inactive;
+        self.arrest = None;
+        self.collapse = None;
+        self.release_velocity = None;
+        self.freeze_displayed(time);
         self.flush(time);
         self.min_tick_period = f64::INFINITY;
+        self.gesture_events = 0;
+    }
+
+    /// Replaces the event history with a short run of synthetic events moving at velocity
+    /// (px/ms) and ending at time, as if a pan had been tracking at that speed
+    pub fn seed_velocity(&mut self, time: Time, velocity: Velocity) {
+        self.signal_interrupt(time);
+
+        // event values are cumulative, so carry on from wherever the last ones left off
+        let base = self.events.back().map_or(self.track_initial_pos, |evt| evt.value);
+        self.events.clear();
+        for k in (0..SEED_EVENT_COUNT).rev() {
+            let back = k as f64 * SEED_EVENT_INTERVAL;
+            self.events.push_back(Event { time: time - back, value: base - velocity * back });
+        }
+
+        self.min_tick_period = SEED_EVENT_INTERVAL;
+        self.gesture_events = SEED_EVENT_COUNT;
+        self.current_phase = Phase::Interpolating;
+    }
+
+    /// Brings a fling in progress to a stop over the next duration ms, easing out rather than
+    /// stopping dead. Does nothing unless flinging
+    pub fn arrest(&mut self, time: Time, duration: TimeDelta) {
+        if !matches!(self.current_phase, Phase::Released(_)) {
+            return;
+        }
+
+        let velocity = self.samples.back().map_or(0.0, |s| s.velocity);
+        self.arrest = Some((time, duration, velocity.abs()));
+    }
+
+    /// How much of its starting speed the fling in progress has shed, from 0 to 1, or None if
+    /// not flinging
+    pub fn fling_progress(&self) -> Option<f64> {
+        let initial = match (self.current_phase, self.fling_start_velocity) {
+            (Phase::Released(_), Some(initial)) if initial != 0.0 => initial,
+            _ => return None,
+        };
+        let velocity = self.samples.back().map_or(0.0, |s| s.velocity);
+
+        Some((1.0 - velocity.abs() / initial.abs()).clamp(0.0, 1.0))
+    }
+
+    /// Sets the fling in progress going at the speed it would have at the given progress, see
+    /// fling_progress(). Does nothing if not flinging
+    pub fn set_fling_progress(&mut self, progress: f64) {
+        let initial = match (self.current_phase, self.fling_start_velocity) {
+            (Phase::Released(_), Some(initial)) => initial,
+            _ => return,
+        };
+
+        if let Some(last) = self.samples.back_mut() {
+            last.velocity = initial * (1.0 - progress.clamp(0.0, 1.0));
+        }
+    }
+
+    /// Speed the fling in progress started at, for weighing axes against each other
+    pub fn fling_start_speed(&self) -> f64 {
+        self.fling_start_velocity.map_or(0.0, f64::abs)
     }
 
     pub fn signal_pan(&mut self, time: Time, delta: f64) {
@@ -286,10 +934,13 @@ impl Interpolator {
         }
         //println!("Push pan");
         self.current_phase = Phase::Interpolating;
+        self.arrest = None;
+        self.collapse = None;
 
         let (prev_val, prev_time) = self.events.back().map(|evt| (evt.value, evt.time)).unwrap_or((self.track_initial_pos, f64::NEG_INFINITY));
 
         let current_val = prev_val + delta;
+        self.total_scrolled += delta.abs();
 
         if time - prev_time <= 0.0 {
             // some events got bunched up, redistribute prior event halfway between current and
@@ -315,12 +966,17 @@ impl Interpolator {
             self.events.pop_back();
         } else {
             self.min_tick_period = time - prev_time;
+            self.gesture_events += 1;
         }
 
         self.events.push_back(Event { value: current_val, time });
     }
 
     pub fn animating(&self) -> bool {
+        if self.frozen {
+            return false;
+        }
+
         let r = match self.current_phase {
             Phase::Inactive => false,
             _ => true,
@@ -334,10 +990,441 @@ impl Interpolator {
     pub fn set_geometry(&mut self, min: f64, max: f64) {
         self.track_bound_upper = max;
         self.track_bound_lower = min;
+
+        // a fling that now has nowhere to go eases back in from wherever it had got to
+        match (self.current_phase, self.samples.back()) {
+            (Phase::Released(_), Some(last)) if !self.scrollable() && self.collapse.is_none() => {
+                self.collapse = Some((last.time, last.position));
+                self.arrest = None;
+            },
+            _ if self.scrollable() => self.collapse = None,
+            _ => {}
+        }
+
+        // the bound being bounced off has moved out past the content, so the spring would now
+        // pull toward the new edge instead. Let the fling carry on decaying into the new space
+        if let BounceState::Bouncing(edge) = self.bouncing {
+            let position = self.position();
+            let inside = match edge {
+                TrackPosition::Top => position >= min,
+                TrackPosition::Bottom => position <= max,
+            };
+
+            if inside {
+                self.bouncing = BounceState::Normal;
+                self.spring_motion = None;
+            }
+        }
+    }
+
+    /// Lower and upper bounds of the track
+    pub fn bounds(&self) -> (f64, f64) {
+        (self.track_bound_lower, self.track_bound_upper)
+    }
+
+    /// The position handed out by the most recent sample()
+    pub fn position(&self) -> Position {
+        self.samples.back().map(|s| s.position).unwrap_or(0.0)
+    }
+
+    /// Position to draw at, which is position() unless output interpolation is smoothing it
+    pub fn displayed_position(&self) -> Position {
+        match self.displayed {
+            Some((_, shown, _, _)) => shown,
+            None => self.position(),
+        }
+    }
+
+    pub fn set_collapse_duration(&mut self, duration: TimeDelta) {
+        self.collapse_duration = duration;
+    }
+
+    pub fn set_settle_frames(&mut self, frames: usize) {
+        self.settle_frames = frames;
+    }
+
+    pub fn set_exact_rest(&mut self, enabled: bool) {
+        self.exact_rest = enabled;
+    }
+
+    pub fn set_output_interpolation(&mut self, enabled: bool) {
+        self.output_interpolation = enabled;
+        self.displayed = None;
+    }
+
+    /// Moves the whole track history by delta, leaving velocity untouched
+    pub fn offset(&mut self, delta: Position) {
+        for sample in self.samples.iter_mut() {
+            sample.position += delta;
+        }
+        for event in self.events.iter_mut() {
+            event.value += delta;
+        }
+        if let BounceState::Settling(target) = self.bouncing {
+            self.bouncing = BounceState::Settling(target + delta);
+        }
+        if let Some(limit) = self.outward_fling_limit {
+            self.outward_fling_limit = Some(limit + delta);
+        }
+        if let Some((start, from)) = self.collapse {
+            self.collapse = Some((start, from + delta));
+        }
+        if let Some((time, position)) = self.tracked {
+            self.tracked = Some((time, position + delta));
+        }
+        if let Some((time, shown, target, velocity)) = self.displayed {
+            self.displayed = Some((time, shown + delta, target + delta, velocity));
+        }
+    }
+
+    /// Swaps the model flings decay under. A fling already underway keeps its velocity and
+    /// eases from the old model's deceleration to the new one's over MODEL_BLEND_MS, rather
+    /// than changing how hard it brakes all at once
+    pub fn set_fling_model(&mut self, model: crate::FlingModel) {
+        if model == self.fling_model {
+            return;
+        }
+
+        self.model_blend = match (self.current_phase, self.samples.back()) {
+            (Phase::Released(_), Some(last)) => Some((last.time, self.decaying_model(last.time))),
+            _ => None,
+        };
+        self.fling_model = model;
+    }
+
+    // the model blended out of stays whichever one was winning when it's itself interrupted
+    // by another change, which is close enough for a window this short
+    fn decaying_model(&self, time: Time) -> crate::FlingModel {
+        match self.model_blend {
+            Some((start, from)) if time - start < MODEL_BLEND_MS / 2.0 => from,
+            _ => self.fling_model,
+        }
+    }
+
+    pub fn set_lead(&mut self, lead: Option<TimeDelta>) {
+        self.lead_override = lead;
+    }
+
+    /// How far ahead the most recent sample() was led, after confidence and rolloff
+    pub fn lead(&self) -> TimeDelta {
+        self.lead
+    }
+
+    pub fn set_fling_inheritance(&mut self, enabled: bool) {
+        self.fling_inheritance = enabled;
+        if !enabled {
+            self.inherited_velocity = None;
+        }
+    }
+
+    pub fn set_profile(&mut self, profile: crate::ScrollProfile) {
+        self.profile = Some(profile);
+    }
+
+    pub fn set_compaction(&mut self, mode: crate::CompactionMode) {
+        self.compaction = mode;
+    }
+
+    pub fn set_max_sample_age(&mut self, age: TimeDelta) {
+        self.max_sample_age = age.abs();
+    }
+
+    pub fn set_resampling(&mut self, mode: crate::ResampleMode) {
+        self.resample = mode;
+    }
+
+    pub fn set_prediction_rolloff(&mut self, velocity: f64) {
+        self.prediction_rolloff = velocity.abs();
+    }
+
+    pub fn set_jerk_limit(&mut self, limit: f64) {
+        self.jerk_limit = if limit > 0.0 { limit } else { f64::INFINITY };
+    }
+
+    pub fn set_prediction_enabled(&mut self, enabled: bool) {
+        self.prediction_enabled = enabled;
+    }
+
+    pub fn set_epsilon(&mut self, epsilon: f64) {
+        self.epsilon = epsilon.abs();
+    }
+
+    pub fn set_velocity_epsilon(&mut self, epsilon: f64) {
+        self.velocity_epsilon = epsilon.abs();
+    }
+
+    pub fn freeze(&mut self) {
+        self.frozen = true;
+    }
+
+    pub fn frozen(&self) -> bool {
+        self.frozen
+    }
+
+    pub fn unfreeze(&mut self) {
+        if self.frozen {
+            self.frozen = false;
+            self.thawing = true;
+        }
+    }
+
+    /// Lowest and highest positions the track can reach, rubber-banding included
+    pub fn position_range(&self) -> (Position, Position) {
+        let (lower, upper) = (self.track_bound_lower, self.track_bound_upper.max(self.track_bound_lower));
+
+        let slack = match self.source.overscrolls() {
+            true if self.scrollable() || self.empty_overscroll => self.max_overscroll,
+            _ => 0.0,
+        };
+
+        (lower - slack, upper + slack)
+    }
+
+    /// Judges how much of a pan of delta the track has room for, from the latest sample
+    pub fn pan_outcome(&self, delta: f64) -> crate::PanOutcome {
+        if !self.scrollable() && !self.empty_overscroll {
+            return crate::PanOutcome::Rejected;
+        }
+
+        // rubber-banding takes any amount of pan, up to max_overscroll
+        let slack = if self.source.overscrolls() { self.max_overscroll } else { 0.0 };
+        if slack.is_infinite() {
+            return crate::PanOutcome::Consumed;
+        }
+
+        let position = self.position();
+        let room = if delta > 0.0 {
+            self.track_bound_upper + slack - position
+        } else {
+            position - (self.track_bound_lower - slack)
+        }.max(0.0);
+
+        if room <= self.epsilon {
+            crate::PanOutcome::Rejected
+        } else if delta.abs() > room {
+            crate::PanOutcome::PartiallyConsumed { remaining: (delta.abs() - room).copysign(delta) }
+        } else {
+            crate::PanOutcome::Consumed
+        }
+    }
+
+    /// How far the velocity estimate can be trusted, from 0 to 1
+    ///
+    /// Ramps up with the number of events seen in the current gesture, and falls off as the
+    /// velocities between those events disagree with each other
+    pub fn prediction_confidence(&self) -> f64 {
+        let config = self.config();
+
+        let count = self.gesture_events.min(self.events.len());
+        if count < 2 {
+            return 0.0;
+        }
+
+        let recent: Vec<&Event> = self.events.iter().skip(self.events.len() - count).collect();
+        let velocities: Vec<Velocity> = recent
+            .windows(2)
+            .map(|pair| Self::slope_of(pair[0], pair[1]))
+            .collect();
+
+        let mean = velocities.iter().sum::<f64>() / velocities.len() as f64;
+        let variance = velocities.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / velocities.len() as f64;
+
+        let consistency = if mean == 0.0 {
+            if variance == 0.0 { 1.0 } else { 0.0 }
+        } else {
+            1.0 / (1.0 + variance.sqrt() / mean.abs())
+        };
+
+        let ramp = ((count - 1) as f64 / config.CONFIDENCE_FULL_EVENT_COUNT.max(1) as f64).min(1.0);
+
+        ramp * consistency
+    }
+
+    /// Runs velocity through the acceleration curve, regardless of whether the current source
+    /// accelerates
+    pub fn preview_accel(&self, velocity: Velocity) -> Velocity {
+        self.accel_curve(velocity, 1.0)
+    }
+
+    /// Sets the share of each coming pan's movement that's along this axis, from 0 to 1, so
+    /// acceleration goes by the speed of the whole diagonal movement rather than this axis alone
+    pub fn set_axis_share(&mut self, share: f64) {
+        self.axis_share = share;
+    }
+
+    /// Gives what a fling velocity (px/ms) would have decayed to after elapsed ms
+    pub fn preview_decay(&self, velocity: f64, elapsed: TimeDelta) -> f64 {
+        let timestep = sconfig.read().unwrap().TIMESTEP;
+
+        // convert to and from px per half TIMESTEP, the units decay() works in
+        let mut internal = velocity * timestep / 2.0;
+        for (start, end) in iter_range(0.0, elapsed.max(0.0), timestep) {
+            internal = self.decay(start, end, 0.0, internal);
+        }
+
+        internal * 2.0 / timestep
+    }
+
+    pub fn set_grid_snap(&mut self, cell_size: f64, offset: f64) {
+        self.grid = if cell_size > 0.0 { Some((cell_size, offset)) } else { None };
+    }
+
+    pub fn set_end_bias(&mut self, bias: crate::EndBias) {
+        self.end_bias = bias;
+    }
+
+    pub fn set_snap_magnetism(&mut self, strength: f64) {
+        self.magnetism = strength.clamp(0.0, 1.0);
+    }
+
+    /// How far a drag at position is drawn toward the nearest grid boundary
+    ///
+    /// Strongest partway in and none at the boundary itself or past the edge of its reach, so
+    /// content glides in faster as it approaches, lags moving off, and passes smoothly between
+    fn magnet_pull_at(&self, position: Position) -> Position {
+        let (cell_size, _) = match self.grid {
+            Some(grid) if self.magnetism > 0.0 => grid,
+            _ => return 0.0,
+        };
+        let target = match self.snap_target(position) {
+            Some(target) => target,
+            None => return 0.0,
+        };
+
+        let reach = cell_size * MAGNET_RADIUS_CELLS;
+        let gap = target - position;
+        if gap.abs() >= reach {
+            return 0.0;
+        }
+
+        gap * self.magnetism * (1.0 - gap.abs() / reach)
+    }
+
+    pub fn current_cell(&self) -> Option<i64> {
+        self.grid.map(|(cell_size, offset)| ((self.position() - offset) / cell_size).floor() as i64)
+    }
+
+    pub fn total_scrolled(&self) -> f64 {
+        self.total_scrolled
+    }
+
+    pub fn reset_total_scrolled(&mut self) {
+        self.total_scrolled = 0.0;
+    }
+
+    pub fn set_empty_overscroll(&mut self, enabled: bool) {
+        self.empty_overscroll = enabled;
+    }
+
+    pub fn set_max_overscroll(&mut self, distance: f64) {
+        self.max_overscroll = distance.abs();
+    }
+
+    pub fn set_drag_overscroll_resistance(&mut self, resistance: f64) {
+        self.drag_overscroll_resistance = Some(resistance);
+    }
+
+    pub fn set_fling_overscroll_limit(&mut self, distance: f64) {
+        self.fling_overscroll_limit = distance.abs();
+    }
+
+    fn drag_resistance(&self) -> f64 {
+        let config = self.config();
+
+        self.drag_overscroll_resistance.unwrap_or(config.OVERSCROLL_ELASTICITY_COEFFICIENT)
+    }
+
+    pub fn set_accel_range(&mut self, min_velocity: Velocity, max_multiplier: f64) {
+        self.accel_min_velocity = min_velocity.abs();
+        self.accel_max_multiplier = max_multiplier;
+    }
+
+    pub fn set_accel_affects_drag(&mut self, enabled: bool) {
+        self.accel_affects_drag = enabled;
+    }
+
+    pub fn set_fling_multiplier(&mut self, multiplier: f64) {
+        self.fling_multiplier = multiplier;
+    }
+
+    pub fn set_fling_scale_by_extent(&mut self, enabled: bool) {
+        self.fling_scale_by_extent = enabled;
+    }
+
+    /// Multiplier on fling velocity for the current extent, when scaling by extent
+    fn extent_scale(&self) -> f64 {
+        if !self.fling_scale_by_extent {
+            return 1.0;
+        }
+
+        let (min, max) = FLING_EXTENT_SCALE_RANGE;
+        let extent = self.track_bound_upper - self.track_bound_lower;
+
+        (extent / FLING_REFERENCE_EXTENT_PX).clamp(min, max)
+    }
+
+    pub fn accel_range(&self) -> (Velocity, f64) {
+        (self.accel_min_velocity, self.accel_max_multiplier)
+    }
+
+    pub fn fling_model(&self) -> crate::FlingModel {
+        self.fling_model
+    }
+
+    pub fn fling_inheritance(&self) -> bool {
+        self.fling_inheritance
+    }
+
+    pub fn prediction_rolloff(&self) -> f64 {
+        self.prediction_rolloff
+    }
+
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    pub fn velocity_epsilon(&self) -> f64 {
+        self.velocity_epsilon
+    }
+
+    /// The config in effect for this axis: the process-wide one, with any values given by
+    /// set_profile() in place of its own
+    pub fn config(&self) -> crate::Config {
+        let mut config = sconfig.read().unwrap().clone();
+        if let Some(profile) = &self.profile {
+            profile.apply_to(&mut config);
+        }
+
+        config
     }
 }
 // Private impl
 impl Interpolator {
+    /// Drops events and samples older than max_sample_age, always keeping the newest sample so
+    /// position is held, and the events integration on from it interpolates between (input
+    /// resuming after a gap carries on from them, as event values are cumulative). A pan whose
+    /// every event has aged out is stopped where it is, so input resuming after a long gap
+    /// can't fling from stale velocity
+    fn expire(&mut self, time: Time) {
+        if !self.max_sample_age.is_finite() {
+            return;
+        }
+
+        let cutoff = time - self.max_sample_age;
+        while self.samples.get(1).is_some_and(|s| s.time < cutoff) {
+            self.samples.pop_front();
+        }
+
+        let needed = self.samples.back().map_or(cutoff, |last| cutoff.min(last.time + self.lead));
+        while self.events.get(1).is_some_and(|e| e.time < needed) {
+            self.events.pop_front();
+        }
+
+        if self.current_phase == Phase::Interpolating && self.events.back().is_some_and(|e| e.time < cutoff) {
+            self.signal_interrupt(time);
+        }
+    }
+
     /// Empties the sample and event lists, used for interrupt/fling
     /// when a group of samples is logically over (a single "gesture")
     fn flush(&mut self, time: Time) {
@@ -346,26 +1433,145 @@ impl Interpolator {
         //self.samples.clear(); need samples to continue animating
     }
 
+    /// Drops all pan history and any animation, leaving the track at rest wherever it was last
+    /// shown, with nothing measured against earlier timing carried forward
+    pub fn reset(&mut self, time: Time) {
+        let position = self.displayed_position();
+
+        self.events.clear();
+        self.samples.clear();
+        self.samples.push_back(Sample { time, velocity: 0.0, position });
+        self.current_phase = Phase::Inactive;
+        self.bouncing = BounceState::Normal;
+        self.spring_motion = None;
+        self.arrest = None;
+        self.collapse = None;
+        self.model_blend = None;
+        self.magnet_pull = 0.0;
+        self.release_velocity = None;
+        self.inherited_velocity = None;
+        self.fling_start_pending = false;
+        self.fling_start_velocity = None;
+        self.outward_fling_limit = None;
+        self.displayed = None;
+        self.lead = 0.0;
+        self.min_tick_period = f64::INFINITY;
+        self.gesture_events = 0;
+        self.flips_same_value = 0;
+    }
+
+    /// Resets a track whose state has gone non-finite back to rest at its nearest bound,
+    /// since nothing useful can be integrated from there
+    fn recover(&mut self, time: Time) {
+        crate::validate::Validator::recovered();
+
+        let position = self.samples.back()
+            .map(|s| s.position)
+            .filter(|p| p.is_finite())
+            .unwrap_or(self.track_bound_lower)
+            .max(self.track_bound_lower)
+            .min(self.track_bound_upper.max(self.track_bound_lower));
+
+        self.events.clear();
+        self.samples.clear();
+        self.samples.push_back(Sample { time, velocity: 0.0, position });
+        self.current_phase = Phase::Inactive;
+        self.bouncing = BounceState::Normal;
+        self.spring_motion = None;
+        self.lead = 0.0;
+    }
+
+    /// Moves every recorded time forward so the newest sample lands on time, letting
+    /// integration pick up from that sample without covering the gap
+    fn retime(&mut self, time: Time) {
+        let delta = match self.samples.back() {
+            Some(last) => time - last.time,
+            None => return,
+        };
+
+        for sample in self.samples.iter_mut() {
+            sample.time += delta;
+        }
+        for event in self.events.iter_mut() {
+            event.time += delta;
+        }
+        if let Phase::Released(release_time) = self.current_phase {
+            self.current_phase = Phase::Released(release_time + delta);
+        }
+    }
+
+    /// Pins the track to the last position handed out by sample(), so that whatever velocity
+    /// was carried into it (including any overshoot) isn't integrated any further
+    fn freeze_displayed(&mut self, time: Time) {
+        if let Some(&last) = self.samples.back() {
+            self.samples.push_back(Sample { time: time.max(last.time), velocity: 0.0, position: last.position });
+        }
+    }
+
     fn check_idle(&mut self, position: Position, velocity: Velocity) {
-        let config = sconfig.read().unwrap();
+        let config = self.config();
 
         match self.current_phase {
             Phase::Released(_) => {
-                if position == self.last_value || velocity.abs() < config.MIN_VELOCITY_TO_IDLE {
+                // only settle once back within the bounds, and a spring under a jerk limit only
+                // once it's where it's headed (below), as stopping anywhere else would jump
+                let limited_spring = self.jerk_limit.is_finite() && !matches!(self.bouncing, BounceState::Normal);
+                if self.outside_bounds(position) || limited_spring {
+                    self.flips_same_value = 0;
+                } else if (position - self.last_value).abs() <= self.epsilon || velocity.abs() < config.MIN_VELOCITY_TO_IDLE {
                     self.flips_same_value += 1;
                 } else {
                     self.flips_same_value = 0;
                 }
                 self.last_value = position;
 
-                if self.flips_same_value > config.FLIPS_TO_IDLE {
+                // the spring can creep in over the last fraction of a pixel for a long time, or
+                // ring there if mistuned, so once it's been that close and slow for settle_frames
+                // frames in a row it's put where it's headed
+                let (lower, upper) = (self.track_bound_lower, self.track_bound_upper.max(self.track_bound_lower));
+                let spring_rest = match self.bouncing {
+                    BounceState::Settling(target) => Some(target),
+                    BounceState::Bouncing(TrackPosition::Top) => Some(lower),
+                    BounceState::Bouncing(TrackPosition::Bottom) => Some(upper),
+                    BounceState::Normal => None,
+                };
+                // under a jerk limit, close enough and slow enough that stopping dead on the rest
+                // position keeps within the limit even over 1ms frames
+                let calm = match self.jerk_limit.is_finite() {
+                    false => spring_rest.is_some_and(|rest| (position - rest).abs() < SETTLE_GUARD_PX)
+                        && self.negligible_velocity(velocity),
+                    true => spring_rest.is_some_and(|rest| (position - rest).abs() < self.jerk_limit.min(SETTLE_GUARD_PX))
+                        && velocity.abs() * 2.0 / config.TIMESTEP < self.jerk_limit,
+                };
+                self.calm_frames = if calm { self.calm_frames + 1 } else { 0 };
+                let forced = calm && self.calm_frames >= self.settle_frames;
+
+                if forced || self.flips_same_value > config.FLIPS_TO_IDLE {
                     eprintln!("Goes to idle");
                     println!("check_idle goes to Inactive");
                     self.current_phase = Phase::Inactive;
+                    self.calm_frames = 0;
+
+                    // land exactly on the cell boundary or bound rather than wherever the spring
+                    // gave out, and otherwise drop whatever sliver past a bound is left over
+                    let rest = match self.bouncing {
+                        BounceState::Settling(target) => Some(target),
+                        _ if forced => spring_rest,
+                        _ if !self.exact_rest => None,
+                        BounceState::Bouncing(TrackPosition::Top) => Some(lower),
+                        BounceState::Bouncing(TrackPosition::Bottom) => Some(upper),
+                        BounceState::Normal => Some(position.clamp(lower, upper)),
+                    };
+
+                    if let (Some(rest), Some(last)) = (rest, self.samples.back_mut()) {
+                        last.position = rest;
+                        last.velocity = 0.0;
+                    }
                 }
             },
             Phase::Interpolating => {
                 self.flips_same_value = 0;
+                self.calm_frames = 0;
             },
             Phase::Inactive => {}
         }
@@ -378,7 +1584,7 @@ impl Interpolator {
     }*/
 
     fn prevent_coast(&mut self, time: Time) {
-        let config = sconfig.read().unwrap();
+        let config = self.config();
 
         match self.current_phase {
             Phase::Interpolating => match self.events.len() {
@@ -400,20 +1606,19 @@ impl Interpolator {
     }
 
     fn interpolate(&self, time: Time) -> Velocity {
-        let config = sconfig.read().unwrap();
+        let config = self.config();
 
         let first_before = self
             .events
             .iter()
             .filter(|evt| evt.time < time)
-            // round is ok since timestamps have no greater enqueing precision than integrals
-            .max_by(|evt_a, evt_b| (evt_a.time as u64).cmp(&(evt_b.time as u64)));
+            .max_by(|evt_a, evt_b| evt_a.time.total_cmp(&evt_b.time));
 
         let first_after = self
             .events
             .iter()
             .filter(|evt| evt.time >= time)
-            .min_by(|evt_a, evt_b| (evt_a.time as u64).cmp(&(evt_b.time as u64)));
+            .min_by(|evt_a, evt_b| evt_a.time.total_cmp(&evt_b.time));
 
         let second_before = match first_before {
             None => None,
@@ -422,7 +1627,7 @@ impl Interpolator {
                     .events
                     .iter()
                     .filter(|evt| evt.time < first.time)
-                    .max_by(|evt_a, evt_b| (evt_a.time as u64).cmp(&(evt_b.time as u64)))
+                    .max_by(|evt_a, evt_b| evt_a.time.total_cmp(&evt_b.time))
             }
         };
 
@@ -433,7 +1638,7 @@ impl Interpolator {
                     .events
                     .iter()
                     .filter(|evt| evt.time < first.time)
-                    .min_by(|evt_a, evt_b| (evt_a.time as u64).cmp(&(evt_b.time as u64)))
+                    .min_by(|evt_a, evt_b| evt_a.time.total_cmp(&evt_b.time))
             }
         };
 
@@ -457,7 +1662,10 @@ impl Interpolator {
             }
             //1 => Self::interpolate_constant(&events, time),
             2 => Self::interpolate_linear(&events, time),
-            3 => Self::interpolate_linear_averaging(&events, time),
+            3 => match self.resample {
+                crate::ResampleMode::Linear => Self::interpolate_linear_averaging(&events, time),
+                crate::ResampleMode::Averaged => Self::interpolate_averaged(&events, time),
+            },
             4 => Self::interpolate_hermite(&events, time),
             _ => panic!("Programming error: events len greater than 4"),
         };
@@ -475,26 +1683,156 @@ impl Interpolator {
         result
     }
 
+    /// Advances the displayed position toward target along the velocity seen over recent
+    /// frames, so a frame between sparse pans moves as much as the ones around it
+    ///
+    /// Never passes target, since that would be leading ahead (which is prediction's job). Once
+    /// idle it snaps to target so nothing is left trailing after the track stops animating
+    fn smooth_output(&mut self, time: Time, target: Position) {
+        if !self.output_interpolation || self.current_phase == Phase::Inactive {
+            self.displayed = None;
+            return;
+        }
+
+        let (last_time, shown, last_target, velocity) = match self.displayed {
+            Some(displayed) => displayed,
+            None => {
+                self.displayed = Some((time, target, target, 0.0));
+                return;
+            }
+        };
+
+        let dt = time - last_time;
+        if dt <= 0.0 {
+            return;
+        }
+
+        let measured = (target - last_target) / dt;
+        let velocity = velocity + (measured - velocity) * OUTPUT_VELOCITY_WEIGHT;
+
+        let gap = target - shown;
+        let advanced = shown + velocity * dt;
+        let caught_up = shown + gap * OUTPUT_CATCHUP;
+        let next = if gap >= 0.0 {
+            advanced.max(caught_up).min(target)
+        } else {
+            advanced.min(caught_up).max(target)
+        };
+
+        self.displayed = Some((time, next, target, velocity));
+    }
+
+    /// Fraction of the full prediction lead to use at the given velocity, falling off
+    /// inversely once past prediction_rolloff so the distance led ahead stops growing
+    fn rolloff(&self, velocity: Velocity) -> f64 {
+        let config = self.config();
+
+        let speed = velocity.abs() * 2.0 / config.TIMESTEP;
+        if speed <= self.prediction_rolloff {
+            1.0
+        } else {
+            self.prediction_rolloff / speed
+        }
+    }
+
+    /// True if velocity would move the track by less than velocity_epsilon px per millisecond
+    fn negligible_velocity(&self, velocity: Velocity) -> bool {
+        let config = self.config();
+
+        // velocity is in px per half TIMESTEP, see sample()
+        velocity.abs() * 2.0 / config.TIMESTEP < self.velocity_epsilon
+    }
+
+    /// Nearest grid cell boundary to position, kept within the track, if a grid is set
+    ///
+    /// Past the last boundary before the end of the track, the cell left over is shorter than
+    /// the rest, and end_bias decides which side of it to settle on
+    fn snap_target(&self, position: Position) -> Option<Position> {
+        self.grid.map(|(cell_size, offset)| {
+            let upper = self.track_bound_upper.max(self.track_bound_lower);
+            let last_full = ((upper - offset) / cell_size).floor() * cell_size + offset;
+
+            let boundary = match self.end_bias {
+                crate::EndBias::FullCell if position > last_full => last_full,
+                crate::EndBias::TrueEnd if position > last_full => upper,
+                _ => ((position - offset) / cell_size).round() * cell_size + offset,
+            };
+
+            boundary.max(self.track_bound_lower).min(upper)
+        })
+    }
+
+    /// True if the track has any extent to scroll over
+    fn scrollable(&self) -> bool {
+        self.track_bound_upper > self.track_bound_lower
+    }
+
     fn outside_bounds(&self, position: Position) -> bool {
-        let config = sconfig.read().unwrap();
+        let config = self.config();
 
-        position > self.track_bound_upper || position < self.track_bound_lower
+        position > self.track_bound_upper + self.epsilon || position < self.track_bound_lower - self.epsilon
     }
 
     fn short_circuit_single_event(&self) -> Position /* delta */ {
-        let config = sconfig.read().unwrap();
+        let config = self.config();
 
         self.events.back().map(|evt| evt.value).unwrap_or(0.0)
     }
 
     fn fling_boost(&self, velocity: Velocity) -> Velocity {
-        let config = sconfig.read().unwrap();
+        let config = self.config();
 
         velocity * config.FLING_BOOST_CONSTANT_FACTOR
     }
 
+    /// Holds velocity under an envelope falling from the speed an arrest started at to 0 at its
+    /// end, easing out so the stop doesn't jolt
+    ///
+    /// Overscrolled content is left to the spring, so it isn't stranded past the bound
+    fn apply_arrest(&self, time: Time, position: Position, velocity: Velocity) -> Velocity {
+        let (start, duration, initial) = match self.arrest {
+            Some(arrest) if !self.outside_bounds(position) => arrest,
+            _ => return velocity,
+        };
+
+        let remaining = if duration > 0.0 {
+            (1.0 - (time - start) / duration).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let limit = initial * remaining * remaining;
+
+        velocity.clamp(-limit, limit)
+    }
+
+    /// Takes the velocity a fling starts with and, if it would push further out of a bound the
+    /// track is already past, resists it as a pan that deep would be and limits how much
+    /// further it can go, so pulling harder against the edge doesn't throw the content away
+    fn resist_outward_fling(&mut self, position: Position, velocity: Velocity) -> Velocity {
+        if !self.outside_bounds(position) {
+            return velocity;
+        }
+
+        let (outside_by, bound) = if position > self.track_bound_upper && velocity > 0.0 {
+            (position - self.track_bound_upper, self.track_bound_upper)
+        } else if position < self.track_bound_lower && velocity < 0.0 {
+            (self.track_bound_lower - position, self.track_bound_lower)
+        } else {
+            // heading back in already
+            return velocity;
+        };
+
+        let reach = (outside_by + OUTWARD_FLING_MAX_EXTRA_PX).min(self.max_overscroll);
+        self.outward_fling_limit = Some(bound + reach.copysign(velocity));
+
+        velocity * (1.0 / (outside_by * self.drag_resistance())).min(1.0)
+    }
+
     fn handle_overscroll(&self, start: Time, end: Time, position: Position, velocity: Velocity) -> Velocity {
-        let config = sconfig.read().unwrap();
+        if !self.scrollable() && !self.empty_overscroll {
+            // content fits the viewport exactly, so the pan has nowhere to go
+            return 0.0;
+        }
 
         if self.outside_bounds(position) {
             //velocity.abs().powf(0.6).copysign(velocity)
@@ -513,9 +1851,14 @@ impl Interpolator {
                     self.track_bound_lower - position
                 };
 
+                if outside_by >= self.max_overscroll {
+                    // pulled as far as allowed, so any further pan is absorbed
+                    return 0.0;
+                }
+
                 let abs_vel = velocity.abs();
                 let timedelta = end - start;
-                let r_velocity = velocity * (1.0 / (outside_by * config.OVERSCROLL_ELASTICITY_COEFFICIENT));
+                let r_velocity = velocity * (1.0 / (outside_by * self.drag_resistance()));
 
                 /*if reduction_amount < 0.0 {
                     panic!("Reduction amount negative");
@@ -542,37 +1885,98 @@ impl Interpolator {
         }
     }
 
+    /// Limits a fling combined from an inherited one to max_fling_velocity, though never
+    /// below the velocity the new fling had on its own
+    fn cap_inherited(&self, own: Velocity, combined: Velocity) -> Velocity {
+        let config = self.config();
+
+        // max_fling_velocity is in px/ms, velocity here is in px per half TIMESTEP
+        let cap = config.MAX_FLING_VELOCITY * config.TIMESTEP / 2.0;
+
+        combined.abs().min(cap.max(own.abs())).copysign(combined)
+    }
+
     fn accelerate(&self, velocity: Velocity) -> Velocity {
-        let config = sconfig.read().unwrap();
         //velocity
         if self.source.accelerates() {
-            (velocity / config.ACCEL_DECEL_DESCRIMINANT).abs().powf(config.ACCELERATION_EXPONENT).copysign(velocity) * config.ACCEL_DECEL_DESCRIMINANT
+            self.accel_curve(velocity, self.axis_share)
         } else {
             velocity
         }
     }
 
+    /// The acceleration applied to pans from sources that accelerate, given what share of the
+    /// whole movement's speed the velocity is
+    ///
+    /// The curve is worked out on the whole speed and scaled back down by share, so both axes
+    /// of a diagonal are multiplied alike and it keeps its direction
+    fn accel_curve(&self, velocity: Velocity, share: f64) -> Velocity {
+        let config = self.config();
+
+        let abs_vel = velocity.abs() / share;
+        if abs_vel <= self.accel_min_velocity {
+            return velocity;
+        }
+
+        // curve only applies to the part above the deadzone, so there's no step at its edge
+        let excess = abs_vel - self.accel_min_velocity;
+        let accelerated = self.accel_min_velocity
+            + (excess / config.ACCEL_DECEL_DESCRIMINANT).powf(config.ACCELERATION_EXPONENT) * config.ACCEL_DECEL_DESCRIMINANT;
+
+        (accelerated.min(abs_vel * self.accel_max_multiplier) * share).copysign(velocity)
+    }
+
     fn pre_scale(&self, velocity: Velocity) -> Velocity {
-        let config = sconfig.read().unwrap();
+        let config = self.config();
 
         velocity * config.PRE_ACCEL_SCALE_VELOCITY
     }
 
     fn post_scale(&self, velocity: Velocity) -> Velocity {
-        let config = sconfig.read().unwrap();
+        let config = self.config();
 
         velocity * config.POST_ACCEL_SCALE_VELOCITY
     }
 
+    /// Rate at which model sheds velocity, per unit of time, when moving at velocity
+    fn decay_slope(model: crate::FlingModel, velocity: Velocity) -> f64 {
+        match model {
+            crate::FlingModel::Curve => {
+                //let slope = -0.00003 / (old_velocity.log2() + 1.0);
+                let friction_factor = if velocity != 0.0 {
+                    velocity.abs().powf(1.3) / velocity.abs()
+                } else {
+                    0.0
+                };
+
+                -0.00009 * friction_factor
+            },
+            crate::FlingModel::Friction { deceleration } => {
+                let config = sconfig.read().unwrap();
+
+                // deceleration is given in px/s^2, but velocity here is in px per half
+                // TIMESTEP (see sample()), which is where the extra TIMESTEP / 2 comes from
+                -(deceleration / 1_000_000.0) * config.TIMESTEP / 2.0
+            },
+        }
+    }
+
     fn decay(&self, start: Time, end: Time, _position: Position, old_velocity: Velocity) -> Velocity {
         if old_velocity.is_nan() {
             panic!("given NaN velocity");
         }
 
-        if old_velocity == 0.0 {
+        // a spring passes through near-zero velocity at every turn, so leave it be there
+        if self.negligible_velocity(old_velocity) && matches!(self.bouncing, BounceState::Normal) {
             return 0.0;
         }
 
+        // a spring under a jerk limit has its own damping, and friction would only kink its
+        // path wherever it turns
+        if self.spring_motion.is_some() {
+            return old_velocity;
+        }
+
         let timedelta = end - start;
         //println!("DECAY: {}, {}", timedelta, old_velocity);
         let abs_vel = old_velocity.abs();
@@ -581,14 +1985,16 @@ impl Interpolator {
             panic!("Negative timedelta passed to decay");
         }
 
-        //let slope = -0.00003 / (old_velocity.log2() + 1.0);
-        let friction_factor = if old_velocity != 0.0 {
-            old_velocity.abs().powf(1.3) / old_velocity.abs()
-        } else {
-            0.0
+        let slope = match self.model_blend {
+            Some((since, from)) if (since..since + MODEL_BLEND_MS).contains(&start) => {
+                let progress = (start - since) / MODEL_BLEND_MS;
+
+                Self::decay_slope(from, old_velocity) * (1.0 - progress)
+                    + Self::decay_slope(self.fling_model, old_velocity) * progress
+            }
+            _ => Self::decay_slope(self.fling_model, old_velocity),
         };
-                
-        let slope = -0.00009 * friction_factor;
+
         let new_vel = abs_vel + slope * timedelta;
 
         let floored = if new_vel < 0.0 {
@@ -631,7 +2037,7 @@ impl Interpolator {
     }
 
     fn bounce(&mut self, start: Time, end: Time, position: Position, old_velocity: Velocity) -> Velocity {
-        let config = sconfig.read().unwrap();
+        let config = self.config();
 
         if self.outside_bounds(position) {
             let trackposition = if position > self.track_bound_upper {
@@ -641,26 +2047,48 @@ impl Interpolator {
             };
 
             self.bouncing = BounceState::Bouncing(trackposition);
+        } else if self.outward_fling_limit.take().is_some() {
+            // back inside after being thrown outward, so the stiffer spring is done with
+        } else if let (BounceState::Normal, Some(target)) = (self.bouncing, self.snap_target(position)) {
+            // let the fling carry as far as it will, then pull in to whichever boundary it
+            // ended up nearest
+            if old_velocity.abs() < config.SNAP_VELOCITY_THRESHOLD {
+                self.bouncing = BounceState::Settling(target);
+            }
         }
 
         match &self.bouncing {
-            BounceState::Normal => old_velocity,
-            BounceState::Bouncing(trackposition) => {
+            BounceState::Normal => {
+                self.spring_motion = None;
+                old_velocity
+            },
+            BounceState::Bouncing(_) | BounceState::Settling(_) => {
                 if old_velocity.is_nan() {
                     panic!("Given NaN velocity");
                 }
 
-                let displacement = match trackposition {
-                    TrackPosition::Top => position - self.track_bound_lower,
-                    TrackPosition::Bottom => position - self.track_bound_upper,
+                let rest = match self.bouncing {
+                    BounceState::Bouncing(TrackPosition::Top) => self.track_bound_lower,
+                    BounceState::Bouncing(TrackPosition::Bottom) => self.track_bound_upper,
+                    BounceState::Settling(target) => target,
+                    BounceState::Normal => position,
                 };
+                let displacement = position - rest;
 
-                let force = -displacement * config.OVERSCROLL_SPRING_CONSTANT;
+                let stiffness = if self.outward_fling_limit.is_some() {
+                    config.OVERSCROLL_SPRING_CONSTANT * OUTWARD_FLING_SPRING_BOOST
+                } else {
+                    config.OVERSCROLL_SPRING_CONSTANT
+                };
+                let force = -displacement * stiffness;
                 let timedelta = end - start;
                 let acceleration = force / config.CONTENT_MASS_VALUE;
-                let velocity = old_velocity + acceleration * timedelta;
 
-                let velocity = velocity * config.BOUNCE_DAMP_FACTOR;
+                if self.jerk_limit.is_finite() {
+                    return self.jerk_limited_spring(timedelta, rest, displacement, old_velocity, stiffness);
+                }
+
+                let velocity = (old_velocity + acceleration * timedelta) * config.BOUNCE_DAMP_FACTOR;
 
                 if velocity.is_nan() {
                     panic!("Velocity was NaN");
@@ -673,6 +2101,103 @@ impl Interpolator {
         }
     }
 
+    /// Steps a spring under the jerk limit, giving the new velocity
+    ///
+    /// Rather than a damped spring, which takes hold with a jump in acceleration and turns
+    /// sharply wherever it's moving fast, the motion is that of a critically damped third order
+    /// system, so acceleration is itself carried from step to step and only changes smoothly.
+    /// How quickly it closes in is chosen when it comes on (or its rest moves) as the quickest,
+    /// no quicker than the spring itself, whose whole path from there keeps within the limit
+    fn jerk_limited_spring(&mut self, timedelta: TimeDelta, rest: Position, displacement: f64, old_velocity: Velocity, stiffness: f64) -> Velocity {
+        let config = self.config();
+
+        // velocity here is in px per half TIMESTEP, see sample()
+        let to_px = 2.0 / config.TIMESTEP;
+        let velocity = old_velocity * to_px;
+
+        let motion = match self.spring_motion {
+            Some(motion) if motion.rest == rest => motion,
+            previous => {
+                // carry on with the acceleration already under way, whether the spring's own or
+                // whatever the track was last slowing at
+                let acceleration = previous.map_or_else(|| self.recent_acceleration() * to_px, |m| m.acceleration);
+                let natural = (stiffness / config.CONTENT_MASS_VALUE * to_px).sqrt();
+                let pole = Self::jerk_limited_pole(displacement, velocity, acceleration, natural, self.jerk_limit);
+
+                SpringMotion { rest, pole, acceleration }
+            },
+        };
+
+        let pole = motion.pole;
+        let jerk = -pole.powi(3) * displacement - 3.0 * pole.powi(2) * velocity - 3.0 * pole * motion.acceleration;
+        let acceleration = motion.acceleration + jerk * timedelta;
+        self.spring_motion = Some(SpringMotion { acceleration, ..motion });
+
+        (velocity + acceleration * timedelta) / to_px
+    }
+
+    /// Rate of change of velocity between the last two samples, in px per half TIMESTEP per ms
+    fn recent_acceleration(&self) -> f64 {
+        match (self.samples.len().checked_sub(2).and_then(|i| self.samples.get(i)), self.samples.back()) {
+            (Some(before), Some(last)) if last.time > before.time => (last.velocity - before.velocity) / (last.time - before.time),
+            _ => 0.0,
+        }
+    }
+
+    /// Quickest pole, up to natural, at which a critically damped third order system starting
+    /// out displacement px from rest at velocity (px/ms) and acceleration (px/ms²) never has a
+    /// jerk above limit
+    ///
+    /// From there the path is (c0 + c1·t + c2·t²)·e^(-pole·t), so its jerk is a quadratic times
+    /// the same exponential, which peaks either at the start or where its derivative is zero
+    fn jerk_limited_pole(displacement: f64, velocity: f64, acceleration: f64, natural: f64, limit: f64) -> f64 {
+        let peak_jerk = |p: f64| {
+            let c0 = displacement;
+            let c1 = velocity + p * c0;
+            let c2 = (acceleration + 2.0 * p * velocity + p * p * c0) / 2.0;
+
+            // jerk is (alpha + beta·t + gamma·t²)·e^(-p·t)
+            let gamma = -p.powi(3) * c2;
+            let beta = -p.powi(3) * c1 + 6.0 * p * p * c2;
+            let alpha = -p.powi(3) * c0 + 3.0 * p * p * c1 - 6.0 * p * c2;
+            let jerk = |t: f64| (alpha + beta * t + gamma * t * t) * (-p * t).exp();
+
+            // turning points solve -p·gamma·t² + (2·gamma - p·beta)·t + (beta - p·alpha) = 0
+            let (a, b, c) = (-p * gamma, 2.0 * gamma - p * beta, beta - p * alpha);
+            let turns = if a.abs() > f64::EPSILON * (b.abs() + c.abs()) {
+                let discriminant = b * b - 4.0 * a * c;
+                if discriminant >= 0.0 {
+                    let root = discriminant.sqrt();
+                    vec![(-b + root) / (2.0 * a), (-b - root) / (2.0 * a)]
+                } else {
+                    vec![]
+                }
+            } else if b != 0.0 {
+                vec![-c / b]
+            } else {
+                vec![]
+            };
+
+            turns.into_iter().filter(|&t| t > 0.0).map(|t| jerk(t).abs()).fold(alpha.abs(), f64::max)
+        };
+
+        if peak_jerk(natural) <= limit {
+            return natural;
+        }
+
+        // slower is gentler throughout, so home in on the quickest that keeps within the limit
+        let (mut within, mut beyond) = (0.0, natural);
+        for _ in 0..POLE_SEARCH_STEPS {
+            let pole = (within + beyond) / 2.0;
+            match peak_jerk(pole) <= limit {
+                true => within = pole,
+                false => beyond = pole,
+            }
+        }
+
+        within
+    }
+
     fn sample_velocity(&self, start: Time, end: Time) -> Velocity {
         let p1 = self.interpolate(start);
         let p2 = self.interpolate(end);
@@ -693,6 +2218,41 @@ impl Interpolator {
                 r
             },
             Phase::Released(release_time) if release_time < start => {
+                let starting = self.fling_start_pending;
+                let old_velocity = if starting {
+                    self.fling_start_pending = false;
+
+                    // drag went unaccelerated, so the fling takes the acceleration it skipped. A
+                    // tracker's velocity is of the raw input, so always does
+                    let old_velocity = match self.release_velocity.take() {
+                        Some(velocity) => {
+                            // into the units sample_velocity() measures drags in
+                            let timestep = sconfig.read().unwrap().TIMESTEP;
+                            self.post_scale(self.accelerate(self.pre_scale(velocity * timestep * timestep)))
+                        },
+                        None if self.accel_affects_drag => old_velocity,
+                        None => {
+                            let config = self.config();
+                            self.post_scale(self.accelerate(old_velocity / config.POST_ACCEL_SCALE_VELOCITY))
+                        },
+                    };
+
+                    self.resist_outward_fling(position, old_velocity * self.fling_multiplier * self.extent_scale())
+                } else {
+                    old_velocity
+                };
+
+                let old_velocity = match self.inherited_velocity.take() {
+                    Some(inherited) if inherited.signum() == old_velocity.signum() => {
+                        self.cap_inherited(old_velocity, old_velocity + inherited)
+                    },
+                    _ => old_velocity,
+                };
+
+                if starting {
+                    self.fling_start_velocity = Some(old_velocity);
+                }
+
                 let b = self.bounce(
                     start,
                     end,
@@ -703,6 +2263,7 @@ impl Interpolator {
                     end,
                     position,
                     b);
+                let r = self.apply_arrest(end, position, r);
 
                 /*if r.abs() > old_velocity.abs() {
                     panic!("Velocity increased during release");
@@ -714,14 +2275,16 @@ impl Interpolator {
                 // short circuit velocity measurement, velocity is just the accelerated
                 // interpolation velocity
                 self.bouncing = BounceState::Normal;
+                self.spring_motion = None;
+                self.outward_fling_limit = None;
+                let velocity = self.pre_scale(self.sample_velocity(start, end));
+                let velocity = if self.accel_affects_drag { self.accelerate(velocity) } else { velocity };
                 let r = self.post_scale(
                             self.handle_overscroll(
                                 start,
                                 end,
                                 position,
-                                self.accelerate(
-                                    self.pre_scale(
-                                        self.sample_velocity(start, end)))));
+                                velocity));
                 r
             },
             Phase::Inactive => 0.0
@@ -732,6 +2295,8 @@ impl Interpolator {
         println!("set_inactive sets Inactive");
         self.current_phase = Phase::Inactive;
         self.bouncing = BounceState::Normal;
+        self.spring_motion = None;
+        self.collapse = None;
         self.samples.clear();
     }
 }
@@ -749,7 +2314,7 @@ impl Interpolator {
         if first.time == second.time {
             0.0
         } else {
-            (first.value - second.value) / ((first.time as i64) - (second.time as i64)) as f64
+            (first.value - second.value) / (first.time - second.time)
         }
     }
 
@@ -800,6 +2365,15 @@ impl Interpolator {
         avg*/
     }
 
+    /// Averages the lines through each adjacent pair of the three events, so a single
+    /// irregularly timed event pulls the result less far
+    fn interpolate_averaged(events: &[&Event], at: Time) -> f64 {
+        let s1 = Self::sample_linear(events[0], events[1], at);
+        let s2 = Self::sample_linear(events[1], events[2], at);
+
+        (s1 + s2) / 2.0
+    }
+
     fn interpolate_hermite(events: &Vec<&Event>, at: Time) -> f64 {
         println!("Interpolating hermite");
         panic!("not implemented");
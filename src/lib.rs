@@ -9,6 +9,15 @@
  * It expects an estimation of the next frametime as well as how
  * long until the current frame will be rendered. This allows overshoot
  * calculation to take place.
+ *
+ * Coordinates: position is the offset of the viewport's top left corner into the
+ * content, in px. It's 0 with the start of the content in view, and grows toward the
+ * bottom and right, up to the content size less the viewport size. Overscroll past the
+ * top or left is a negative position, and past the bottom or right, a position beyond
+ * that maximum. Every delta a scrollview is given or gives back (pans, wheel steps,
+ * remainders, adjust_position()) is a change in position, so positive values scroll
+ * toward the end of the content and move it up or left on screen. For deltas given the
+ * other way round, as raw touch movement usually is, see set_pan_direction().
  */
 
 extern crate num;
@@ -25,24 +34,102 @@ mod interpolate;
 
 mod ranged_map;
 
+mod validate;
+
+mod profile;
+
+mod clock;
+
+pub use clock::{Clock, MockClock, SystemClock};
+
+mod units;
+
+pub use units::{Dip, Length, Px};
+
+mod filter;
+
+pub use filter::InputFilter;
+
+mod velocity;
+
+pub use velocity::{LeastSquaresTracker, VelocityTracker, WeightedAverageTracker};
+
+mod controller;
+
+pub use controller::{ScrollController, ViewId};
+
+pub use profile::ScrollProfile;
+
+#[cfg(feature = "async")]
+mod driver;
+
+#[cfg(feature = "async")]
+pub use driver::FrameDriver;
+
 use std::ops;
 use interpolate::Interpolator;
 
-type Timestamp = u64;
+/// A point in time that can be handed to a scrollview along with an event
+///
+/// Implemented for the integer types and `f64` (milliseconds, as used throughout the rest of
+/// the library, with `f64` keeping any fraction), `Duration` (since some caller-chosen epoch),
+/// and `Instant`, so that platform timestamps can be passed to the `push_*_at()` methods
+/// without manual conversion
+pub trait Timestamp: Copy {
+    /// The timestamp expressed in milliseconds, relative to whatever epoch the source uses
+    fn as_millis(&self) -> f64;
+}
+
+impl Timestamp for u64 {
+    fn as_millis(&self) -> f64 { *self as f64 }
+}
+
+impl Timestamp for u32 {
+    fn as_millis(&self) -> f64 { *self as f64 }
+}
+
+impl Timestamp for i64 {
+    fn as_millis(&self) -> f64 { *self as f64 }
+}
+
+impl Timestamp for i32 {
+    fn as_millis(&self) -> f64 { *self as f64 }
+}
+
+impl Timestamp for f64 {
+    fn as_millis(&self) -> f64 { *self }
+}
+
+impl Timestamp for std::time::Duration {
+    fn as_millis(&self) -> f64 { self.as_secs_f64() * 1000.0 }
+}
+
+impl Timestamp for std::time::Instant {
+    fn as_millis(&self) -> f64 {
+        // Instants have no absolute value, so measure from a process-wide epoch instead
+        match self.checked_duration_since(*EPOCH) {
+            Some(since) => Timestamp::as_millis(&since),
+            None => -Timestamp::as_millis(&EPOCH.duration_since(*self)),
+        }
+    }
+}
 
 use std::fs::File;
 use std::io::prelude::*;
 
 use std::sync::RwLock;
+use std::collections::{HashMap, HashSet, VecDeque};
 use tini::Ini;
 
 const APP_INFO: AppInfo = AppInfo { name: "libscroll", author: "Sawyer Bergeron" };
 
 #[allow(non_snake_case)]
+#[derive(Clone)]
 pub struct Config {
 
 	pub EVENT_EXPIRY_COUNT: usize,
 	pub SAMPLE_EXPIRY_COUNT: usize,
+	pub CONFIDENCE_FULL_EVENT_COUNT: usize,
 
 	pub TICKS_TO_COAST: f64,
 	pub TIMESTEP: f64,
@@ -59,6 +146,10 @@ pub struct Config {
     pub ACCEL_DECEL_DESCRIMINANT: f64,
     pub ACCELERATION_EXPONENT: f64,
     pub FLING_BOOST_CONSTANT_FACTOR: f64,
+    pub EDGE_TRIGGER_DISTANCE: f64,
+    pub SNAP_VELOCITY_THRESHOLD: f64,
+    pub MAX_FLING_VELOCITY: f64,
+    pub MIN_FLING_VELOCITY: f64,
 
 	pub FLIPS_TO_IDLE: u64,
 }
@@ -72,6 +163,7 @@ impl Default for Config {
 
             EVENT_EXPIRY_COUNT: 20,
             SAMPLE_EXPIRY_COUNT: 20,
+            CONFIDENCE_FULL_EVENT_COUNT: 5,
 
             TICKS_TO_COAST: 1.6,
 
@@ -95,6 +187,10 @@ impl Default for Config {
             ACCEL_DECEL_DESCRIMINANT: 10.0,
             ACCELERATION_EXPONENT: 1.4,
             FLING_BOOST_CONSTANT_FACTOR: 2.0,
+            EDGE_TRIGGER_DISTANCE: 200.0,
+            SNAP_VELOCITY_THRESHOLD: 0.01,
+            MAX_FLING_VELOCITY: 30.0,
+            MIN_FLING_VELOCITY: 0.05,
         }
     }
 }
@@ -102,6 +198,7 @@ impl Default for Config {
 //thread_local!(static CONFIG: RefCell<Config> = RefCell::new(Config::default()));
 lazy_static! {
     static ref CONFIG: RwLock<Config> = RwLock::new(Config::default());
+    static ref EPOCH: std::time::Instant = std::time::Instant::now();
 }
 
 
@@ -140,7 +237,30 @@ const VALUE_MULTIPLIER: f64 = 6.0;
 //// Degree of polynomial used to interpolate velocity
 //const VELOCITY_POLYNOMIAL_DEGREE: usize = 4;
 
-//type Millis = f64;
+/// How heavily each newly observed frame interval is weighted when auto prediction is
+/// smoothing its estimate of the frametime
+const AUTO_PREDICTION_SMOOTHING: f64 = 0.1;
+
+/// Frame intervals longer than this are taken to be pauses in rendering rather than frames,
+/// and are left out of the auto prediction estimate
+const AUTO_PREDICTION_MAX_FRAME_MS: f64 = 250.0;
+
+/// Spacing of the frames advance() steps through
+const ADVANCE_FRAME_MS: f64 = 8.0;
+
+/// Fraction of its delta the first pan of a gesture moves by when the initial pan ramp is on,
+/// growing to the full delta over the ramp duration
+const INITIAL_PAN_RAMP_FRACTION: f64 = 0.25;
+
+/// How far, in px, a pointer may drift in total while still counting as held still
+const HOLD_SLOP_PX: f64 = 8.0;
+
+/// How many of the most recent pan deltas on an axis outlier rejection compares against
+const OUTLIER_HISTORY: usize = 4;
+/// How many recent pan deltas outlier rejection needs before it rejects anything
+const OUTLIER_MIN_HISTORY: usize = 3;
+
+type Millis = f64;
 
 /// Represents a single scrollview and tracks all state related to it.
 //#[derive(Default)]
@@ -150,13 +270,116 @@ pub struct Scrollview {
     viewport_height: f64,
     viewport_width: f64,
 
+    // parts of the viewport covered by sticky headers, safe areas and the like, which
+    // content can be scrolled out from under at each bound
+    inset_top: f64,
+    inset_bottom: f64,
+    inset_left: f64,
+    inset_right: f64,
+
     current_source: Source,
 
+    drag_sensitivity: f64,
+
+    // time of the first pan since the last interrupt, fling or release, and how long pans are
+    // ramped in for from then on
+    pan_started_at: Option<f64>,
+    initial_pan_ramp_ms: Millis,
+
+    // while a pointer is down, when it was last moved further than HOLD_SLOP_PX and how far it
+    // has drifted since
+    hold_since: Option<f64>,
+    hold_drift: AxisVector<f64>,
+    hold_duration_ms: Millis,
+    // time given to the most recent step_frame(), which is_holding() is judged at
+    last_step_time: Option<f64>,
+
+    // position shown and velocity (px/ms) measured at the previous frame, and the change in
+    // velocity between the last two, for get_acceleration()
+    frame_position: Option<(f64, AxisVector<f64>)>,
+    frame_velocity: Option<AxisVector<f64>>,
+    frame_acceleration: AxisVector<f64>,
+
+    // which way round pushed deltas are, flipped into position terms on the way in
+    pan_direction: ScrollDirection,
+
+    // with AxisLockMode::Exclusive, the axis that claimed the current gesture
+    axis_lock: AxisLockMode,
+    claimed_axis: Option<Axis>,
+
+    // run over every pan delta in order, with the time of the last pan on each axis for dt
+    input_filters: Vec<Box<dyn InputFilter>>,
+    last_pan_time: (Option<f64>, Option<f64>),
+
+    // with outlier rejection on, how many times the recent size of pans on an axis one has to
+    // be to be dropped, those recent sizes, and whether the last pan on each axis was dropped
+    outlier_factor: Option<f64>,
+    recent_deltas: (VecDeque<f64>, VecDeque<f64>),
+    outlier_dropped: (bool, bool),
+
+    // how pans and wheel events pushed before the same frame are combined, whether the pan
+    // being pushed came in through push_wheel(), and how far each of pans and wheel events
+    // have moved the content since the last step_frame()
+    mixed_input: MixedInputPolicy,
+    wheel_input: bool,
+    frame_source_movement: (f64, f64),
+
+    // for anything randomized to draw from, so replays come out the same, see set_seed()
+    rng: SeededRng,
+
+    // measures release velocity in place of the interpolators' own estimate, if set
+    velocity_tracker: Option<Box<dyn VelocityTracker>>,
+
+    // counts for pans pushed since the last step_frame(), and the ones it took in
+    pending_input_stats: InputStats,
+    input_stats: InputStats,
+
+    // physical pixels per device independent pixel
+    scale_factor: f64,
+
+    // element kept still by notify_anchor_moved(), as its id and last known top
+    scroll_anchor: Option<(u64, f64)>,
+
+    // how much push_pan_pressure() deltas grow with pressure
+    pressure_sensitivity: f64,
+
+    // scales applied by push_wheel() to deltas with each modifier held
+    wheel_precision_factor: f64,
+    wheel_fast_factor: f64,
+
+    // absolute pan distance pushed on each axis since the last interrupt
+    gesture_movement: AxisVector<f64>,
+
+    // how the last fling or release was classified, None before any or after one that only
+    // ended a zoom
+    last_gesture_kind: Option<GestureKind>,
+
+    // pointers currently down, as seen through push_pan_multi()
+    pointers: HashSet<u64>,
+
+    // where push_pointer() last put the pointer, since the last interrupt, fling or release
+    pointer_position: Option<(f64, f64)>,
+
+    // when on, push_zoom_end() releases rather than drops the pans pushed during the pinch
+    zoom_pan_momentum: bool,
+
+    // pans and flings within zoom_lockout_ms after the time given to push_zoom_end() are dropped
+    zoom_lockout_ms: Millis,
+    zoom_ended_at: Option<f64>,
+
     dbg_amt_x: f64,
     dbg_amt_y: f64,
 
-    //frametime: Millis,
-    //time_to_pageflip: Millis,
+    // the lead given through set_next_frame_predict(), or None to use shift_window_ms
+    time_to_pageflip: Option<Millis>,
+
+    // when auto_prediction is on, frametime is estimated from the spacing of mark_frame() calls
+    // and used as the lead in place of time_to_pageflip
+    auto_prediction: bool,
+    frametime: Millis,
+    last_frame_time: Option<f64>,
+    // whether mark_frame() has been called since the last step_frame()
+    frame_marked: bool,
 
     //current_timestamp: u64,
 
@@ -164,10 +387,81 @@ pub struct Scrollview {
 
     input_per_frame_log: circular_backqueue::ForgetfulLogQueue<u32>,
 
+    edge_hooks: HashMap<Edge, EdgeHook>,
+
+    // registered overscroll triggers, and the ids of those that crossed their distance (either
+    // way) during the latest step
+    overscroll_triggers: Vec<OverscrollTrigger>,
+    triggered: Vec<u64>,
+
+    // run once on each transition from animating to not, as seen from step_frame()
+    on_settle: Option<Box<dyn FnMut(AxisVector<f64>) + Send>>,
+    on_scroll_begin: Option<Box<dyn FnMut() + Send>>,
+    on_scroll_end: Option<Box<dyn FnMut() + Send>>,
+    was_animating: bool,
+
+    validator: validate::Validator,
+
+    // consulted for events pushed without a timestamp
+    clock: Box<dyn Clock>,
+
     x: Interpolator,
     y: Interpolator,
 }
 
+/// Checks caller-supplied values before they can reach any state, since a single NaN would
+/// otherwise propagate into every position from then on
+///
+/// Gives false if any are NaN or infinite, in which case the input should be dropped. This is
+/// reported as misuse, see Validator
+fn finite_input(by: &str, values: &[f64]) -> bool {
+    let finite = values.iter().all(|v| v.is_finite());
+    if !finite {
+        validate::Validator::non_finite(by, values);
+    }
+
+    finite
+}
+
+/// Callback registered through set_on_reach_edge(), along with whether it may fire again
+struct EdgeHook {
+    callback: Box<dyn FnMut() -> Option<f64> + Send>,
+    // disarmed after firing, rearmed once the position leaves the trigger distance
+    armed: bool,
+}
+
+/// Overscroll distance registered through add_overscroll_trigger()
+struct OverscrollTrigger {
+    edge: Edge,
+    distance: f64,
+    id: u64,
+    // whether the overscroll past edge was at least distance as of the last step
+    armed: bool,
+}
+
+/// Per-view source of randomness, seeded through set_seed() so replays come out the same
+struct SeededRng {
+    seed: u64,
+    state: u64,
+}
+
+impl SeededRng {
+    fn new(seed: u64) -> SeededRng {
+        SeededRng { seed, state: seed }
+    }
+
+    /// Next value in the sequence for this seed, by splitmix64
+    // nothing randomized draws from this yet, see set_seed()
+    #[allow(dead_code)]
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
 /// Describes a vector in terms of its 2 2d axis magnitudes,
 /// used often to describe transforms and offsets
 #[derive(Copy)]
@@ -212,16 +506,20 @@ impl<T> AxisVector<T> where T: num::Num, T: PartialOrd, T: Copy {
             Axis::Vertical => self.y = magnitude + self.y,
         }
     }*/
-}
 
-impl AxisVector<f64> {
-    pub fn scale(&self, scalar: f64) -> AxisVector<f64> {
+    /// Apply f to each component, keeping everything else as it is
+    pub fn map(self, f: impl Fn(T) -> T) -> AxisVector<T> {
         AxisVector {
-            x: self.x * scalar,
-            y: self.y * scalar,
-            ..self.clone()
+            x: f(self.x),
+            y: f(self.y),
+            ..self
         }
     }
+
+    /// Multiply both components by scalar
+    pub fn scale(&self, scalar: T) -> AxisVector<T> {
+        self.map(|v| v * scalar)
+    }
 }
 
 // TODO: consider naming, doing pythagorean add on + may make more sense, with alternative op to
@@ -239,6 +537,40 @@ impl<T> ops::Add<AxisVector<T>> for AxisVector<T> where T: num::Num, T: PartialO
     }
 }
 
+/// Component-wise, so each axis is only ever combined with the same axis of rhs
+impl<T> ops::Sub<AxisVector<T>> for AxisVector<T> where T: num::Num, T: PartialOrd, T: Copy {
+    type Output = AxisVector<T>;
+
+    fn sub(self, rhs: AxisVector<T>) -> AxisVector<T> {
+        AxisVector {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            ..self
+        }
+    }
+}
+
+/// Component-wise, for scaling each axis by its own factor
+impl<T> ops::Mul<AxisVector<T>> for AxisVector<T> where T: num::Num, T: PartialOrd, T: Copy {
+    type Output = AxisVector<T>;
+
+    fn mul(self, rhs: AxisVector<T>) -> AxisVector<T> {
+        AxisVector {
+            x: self.x * rhs.x,
+            y: self.y * rhs.y,
+            ..self
+        }
+    }
+}
+
+impl<T> ops::Neg for AxisVector<T> where T: num::Num, T: PartialOrd, T: Copy, T: ops::Neg<Output = T> {
+    type Output = AxisVector<T>;
+
+    fn neg(self) -> AxisVector<T> {
+        self.map(|v| -v)
+    }
+}
+
 impl<T> std::fmt::Display for AxisVector<T>
     where T: std::fmt::Display, T: num::Num, T: PartialOrd, T: Copy
 {
@@ -247,12 +579,30 @@ impl<T> std::fmt::Display for AxisVector<T>
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Axis {
     Horizontal,
     Vertical,
 }
 
+/// One of the four ends of the scrollable content
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Edge {
+    fn axis(&self) -> Axis {
+        match self {
+            Self::Top | Self::Bottom => Axis::Vertical,
+            Self::Left | Self::Right => Axis::Horizontal,
+        }
+    }
+}
+
 /// Pass along with any events to indicate what kind of device the event came from
 #[derive(Copy, Clone, Debug)]
 //#[derive(Clone)]
@@ -310,16 +660,336 @@ impl Default for Source {
     fn default() -> Self { Source::Undefined }
 }
 
+/// Describes what happens to the oldest pan events once the log reaches event_expiry_count
+#[derive(Copy, Clone, Debug, Default)]
+pub enum CompactionMode {
+    /// Drop the oldest events outright. This is the default
+    #[default]
+    Discard,
+    /// Thin out the older half of the log by dropping every other event, so some history
+    /// survives at decreasing density with age while memory stays bounded
+    Downsample,
+}
+
+/// Describes how pan input, arriving whenever the device reports it, is resampled to the times
+/// frames are stepped at
+#[derive(Copy, Clone, Debug, Default)]
+pub enum ResampleMode {
+    /// Interpolate linearly between the two events either side of each frame, extrapolating
+    /// from the newest two when a frame lands after them, much as Android does. This is the
+    /// default
+    #[default]
+    Linear,
+    /// Average the lines through the three events around each frame, smoothing out input that
+    /// arrives at an irregular rate at some cost to how tightly it tracks
+    Averaged,
+}
+
+/// Modifier held during a wheel event, as given to push_wheel()
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum WheelModifier {
+    /// No modifier, the wheel scrolls normally
+    #[default]
+    None,
+    /// Fine scrolling (commonly Alt), scaled by set_wheel_precision_factor()
+    Precision,
+    /// Fast scrolling (commonly Ctrl), scaled by set_wheel_fast_factor()
+    Fast,
+}
+
+/// How pans and wheel events are combined when both arrive before the same step_frame(), as
+/// from hybrid devices that report a touchpad and a wheel at once
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum MixedInputPolicy {
+    /// Every delta moves the content and counts toward fling velocity, whichever source it
+    /// came from
+    #[default]
+    Merge,
+    /// Every delta moves the content, but only the source that has moved it furthest so far
+    /// in the frame counts toward fling velocity. The other's deltas are applied as with
+    /// adjust_position(), so they can't skew a fling
+    DominantVelocity,
+    /// A frame takes input from one source only, whichever moved first in it. The other's
+    /// deltas in that frame are dropped
+    Separate,
+}
+
+/// An axis aligned rectangle, in px
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Every form of the position along one axis a renderer may want, as given by
+/// position_report(), all taken from the same frame
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PositionReport {
+    /// Position to draw at, in px, prediction included. The same as get_pos_px()
+    pub predicted: f64,
+    /// Position in px as tracked so far, without being led ahead by prediction
+    pub tracked: f64,
+    /// predicted as a share of the scroll extent, from 0 at the start of the content to 1 at
+    /// the end. Held within 0..1 while overscrolled, and 0 if the axis can't scroll
+    pub fraction: f64,
+    /// How far, in px, predicted is out past the nearest bound, negative past the start of the
+    /// content and positive past its end. 0 within the bounds
+    pub overscroll: f64,
+    /// Index of the grid cell the position falls in, if a grid is set, see set_grid_snap()
+    pub cell: Option<i64>,
+}
+
+/// Everything needed to draw content for the current frame, as given by render_transform()
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RenderTransform {
+    /// Translation to apply to the content, in px. This is the viewport position negated, with
+    /// prediction and any overscroll already included
+    pub translate_x: f64,
+    pub translate_y: f64,
+    /// Scale to apply to the content about its origin, after translation. Scrollviews don't
+    /// zoom, so this is always 1.0 for now
+    pub scale: f64,
+}
+
+impl RenderTransform {
+    /// Gives the translation split into whole pixels and the fraction left over, as
+    /// `((x, y), (fraction_x, fraction_y))`
+    ///
+    /// Positions are kept in f64, which stays precise to well under a pixel even tens of
+    /// millions of pixels from the origin, but f32 (as used by most gpu pipelines) can't hold
+    /// any fraction of a pixel past about 8 million, so slow scrolls out there visibly snap.
+    /// Renderers in f32 should place content relative to the whole pixel offset and apply only
+    /// the fraction in f32
+    pub fn translate_split(&self) -> ((i64, i64), (f32, f32)) {
+        let (whole_x, whole_y) = (self.translate_x.floor(), self.translate_y.floor());
+
+        (
+            (whole_x as i64, whole_y as i64),
+            ((self.translate_x - whole_x) as f32, (self.translate_y - whole_y) as f32),
+        )
+    }
+}
+
+/// Which way round the deltas given to push_pan() and the like are
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum ScrollDirection {
+    /// Deltas are changes in position, so positive values scroll toward the end of the content
+    /// (moving it up or left on screen), as wheel deltas usually are
+    #[default]
+    Position,
+    /// Deltas are movement of the content on screen, so positive values drag it down or right
+    /// and scroll back toward its start, as raw touch movement usually is
+    Content,
+}
+
+/// Where a fling settles when it comes to rest in the last grid cell, where that's cut short
+/// by the end of the content, see set_end_bias()
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum EndBias {
+    /// The nearer of the cell's boundaries as if it weren't cut short, held within the content,
+    /// so the end is only reached from past the middle of a whole cell. This is the default
+    #[default]
+    Nearest,
+    /// The boundary before it, so a whole cell (a full page, for a grid of viewport size) is
+    /// shown. The true end is still reached by dragging
+    FullCell,
+    /// The end of the content, so a fling that comes to rest that far always reaches it
+    TrueEnd,
+}
+
+/// How pans on one axis affect the other over the course of a gesture
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum AxisLockMode {
+    /// Each axis takes the pans pushed for it
+    #[default]
+    Free,
+    /// The first axis to move after an interrupt claims the gesture, and every pan until the
+    /// next fling, release or interrupt is applied to it, whichever axis it was pushed for
+    Exclusive,
+}
+
+/// How the most recent gesture ended, as reported by last_gesture_kind()
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GestureKind {
+    /// Let go while still moving fast enough to fling on
+    Fling,
+    /// Dragged some distance, then let go too slowly to fling
+    DragStop,
+    /// Let go having barely moved, about where it went down
+    Tap,
+}
+
+/// What a scrollview could make of a pushed pan
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PanOutcome {
+    /// The whole pan moved (or rubber-banded) the content
+    Consumed,
+    /// The content reached a bound it can't overscroll past partway through the pan, leaving
+    /// `remaining` (signed the same way as the pan) unused
+    PartiallyConsumed { remaining: f64 },
+    /// The content can't move in the direction of the pan at all, either because it's already
+    /// at a hard bound or because the axis isn't scrollable
+    Rejected,
+}
+
+/// Counts of the pans that fed one frame, as reported by input_stats()
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputStats {
+    /// Pans pushed, on either axis, including any counted below
+    pub received: u32,
+    /// Pans that landed on the same timestamp as the previous one on their axis, so were
+    /// merged into it rather than tracked as a separate event
+    pub coalesced: u32,
+    /// Pans thrown away without moving anything, for being non-finite, for arriving during
+    /// the lockout after a zoom, for being outliers (see set_outlier_rejection()), or for
+    /// coming from the second source into a frame under MixedInputPolicy::Separate
+    pub dropped: u32,
+}
+
+/// Something about the geometry given to set_geometry() that's likely a mistake, as reported
+/// by geometry_warnings()
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GeometryWarning {
+    /// The content or viewport was given a negative size on the axis
+    NegativeSize(Axis),
+    /// The content has no size on the axis, as if it hasn't been laid out yet
+    EmptyContent(Axis),
+    /// The viewport has no size on the axis, so nothing can be seen through it
+    EmptyViewport(Axis),
+    /// The viewport is larger than the content, so the axis can't scroll. Often intended (for
+    /// short content), so only worth acting on where the axis was expected to scroll
+    ViewportExceedsContent(Axis),
+}
+
+/// Describes how a fling sheds velocity after release
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlingModel {
+    /// Friction that grows with velocity, so fast flings slow quickly at first and then glide.
+    /// This is the default
+    #[default]
+    Curve,
+    /// Constant deceleration in px/s², so a fling released at v px/s travels exactly
+    /// v² / (2 * deceleration) before stopping
+    Friction { deceleration: f64 },
+}
+
 // pub interface
 impl Scrollview {
     /// Gives the current best estimate for the position of the content relative to
     /// the viewport in device pixels
-    pub fn sample(&mut self, timestamp: Timestamp) -> AxisVector<f64> {
+    ///
+    /// Shorthand for mark_frame(), step_frame(), and then get_position_absolute()
+    pub fn sample<T: Timestamp>(&mut self, timestamp: T) -> AxisVector<f64> {
+        self.mark_frame(timestamp);
+        self.step_frame(timestamp);
+
+        self.get_position_absolute()
+    }
+
+    /// Snapshot timing for the upcoming frame, which is to be stepped to at timestamp
+    ///
+    /// This is what auto prediction measures frame spacing from. Call once per frame, before
+    /// step_frame(). If step_frame() is called without it, the frame is marked then
+    pub fn mark_frame<T: Timestamp>(&mut self, timestamp: T) {
+        self.update_prediction(timestamp.as_millis());
+        self.frame_marked = true;
+    }
+
+    /// Account for any newly pushed events and advance any ongoing animation up to
+    /// timestamp
+    ///
+    /// Afterward, get_position_absolute() gives where the content should be drawn
+    pub fn step_frame<T: Timestamp>(&mut self, timestamp: T) {
+        self.step_at(timestamp.as_millis());
+    }
+
+    /// Run the scrollview forward by duration_ms with no further input, as if step_frame() were
+    /// called every 8ms over that span (with the last frame landing exactly at its end), and
+    /// give the position it ends at
+    ///
+    /// For headless rendering and simulations, where there are no real frames to step on.
+    /// Starts from the most recent step_frame(), or from the clock if there hasn't been one
+    pub fn advance(&mut self, duration_ms: f64) -> AxisVector<f64> {
+        if !finite_input("advance()", &[duration_ms]) {
+            return self.get_position_absolute();
+        }
+
+        let mut time = self.last_step_time.unwrap_or_else(|| self.clock.now());
+        let end = time + duration_ms.max(0.0);
+        while time < end {
+            time = (time + ADVANCE_FRAME_MS).min(end);
+            self.step_at(time);
+        }
+
+        self.get_position_absolute()
+    }
+
+    fn step_at(&mut self, time: f64) {
+        if !self.frame_marked {
+            self.update_prediction(time);
+        }
+        self.frame_marked = false;
+        self.last_step_time = Some(time);
+        self.input_stats = std::mem::take(&mut self.pending_input_stats);
+        self.frame_source_movement = (0.0, 0.0);
+
+        self.validator.sampled();
+
+        if !DEBUG {
+            // each axis keeps its own phase and bounce state, so when a diagonal fling reaches a
+            // corner the bounded axis springs back while the other carries on unaffected
+            self.x.sample(time);
+            self.y.sample(time);
+
+            // may move the content if an edge hook prepends to it
+            self.check_edges();
+            self.check_overscroll_triggers();
+            self.check_settled();
+        }
+
+        self.measure_motion(time);
+    }
+
+    fn measure_motion(&mut self, time: f64) {
+        let position = self.get_position_absolute();
+
+        match self.frame_position {
+            Some((previous, _)) if time <= previous => return,
+            Some((previous, last)) => {
+                let elapsed = time - previous;
+                let velocity = (position - last).scale(1.0 / elapsed);
+
+                self.frame_acceleration = self.frame_velocity
+                    .map_or_else(AxisVector::default, |last| (velocity - last).scale(1.0 / elapsed));
+                self.frame_velocity = Some(velocity);
+            }
+            None => {}
+        }
+
+        self.frame_position = Some((time, position));
+    }
+
+    /// Gives how fast the content's velocity changed between the last two frames, in px/ms²
+    /// along each axis, signed the same way as positions
+    ///
+    /// Measured from where the content was shown at each step_frame(), prediction included, so
+    /// it matches what's on screen. Zero until there have been three frames to compare. For
+    /// effects that react to the motion itself, like squash and stretch or speed lines
+    pub fn get_acceleration(&self) -> AxisVector<f64> {
+        self.frame_acceleration
+    }
+
+    /// Gives the position produced by the most recent step_frame(), without advancing anything
+    ///
+    /// Idempotent, so it can be called whenever is convenient after step_frame()
+    pub fn get_position_absolute(&self) -> AxisVector<f64> {
         if !DEBUG {
             AxisVector {
-                //x: self.x.sample(timestamp as f64) * VALUE_MULTIPLIER,
-                x: 0.0,
-                y: self.y.sample(timestamp as f64),
+                x: self.x.displayed_position(),
+                y: self.y.displayed_position(),
                 ..Default::default()
             }
         } else {
@@ -330,6 +1000,83 @@ impl Scrollview {
             }
         }
     }
+
+    /// Gives the transform to apply to the content this frame, composing everything the
+    /// scrollview models into one place
+    ///
+    /// Like get_position_absolute(), this reflects the most recent step_frame()
+    pub fn render_transform(&self) -> RenderTransform {
+        let position = self.get_position_absolute();
+
+        RenderTransform {
+            translate_x: -position.x,
+            translate_y: -position.y,
+            scale: 1.0,
+        }
+    }
+
+    /// Gives the region of content, in content coordinates, that the viewport currently shows
+    ///
+    /// Reflects the most recent step_frame(), prediction included. While overscrolled the rect
+    /// reaches past the content on that side, so intersect it with the content bounds before
+    /// culling if that matters
+    pub fn visible_rect(&self) -> Rect {
+        let position = self.get_position_absolute();
+
+        Rect {
+            x: position.x,
+            y: position.y,
+            width: self.viewport_width,
+            height: self.viewport_height,
+        }
+    }
+
+    /// Component of get_position_absolute() along the given axis, in physical pixels
+    pub fn get_pos_px(&self, axis: Axis) -> Px {
+        let position = self.get_position_absolute();
+
+        Px(match axis {
+            Axis::Horizontal => position.x,
+            Axis::Vertical => position.y,
+        })
+    }
+
+    /// Component of get_position_absolute() along the given axis, in device independent pixels
+    pub fn get_pos_dip(&self, axis: Axis) -> Dip {
+        Dip(self.get_pos_px(axis).0 / self.scale_factor)
+    }
+
+    /// Horizontal component of get_position_absolute()
+    pub fn get_pos_x(&self) -> f64 {
+        self.get_position_absolute().x
+    }
+
+    /// Vertical component of get_position_absolute()
+    pub fn get_pos_y(&self) -> f64 {
+        self.get_position_absolute().y
+    }
+
+    /// Gives the predicted position at each of the next `steps` intervals of `interval_ms`,
+    /// starting from the last sample and assuming no new input arrives
+    ///
+    /// Runs the same integration as step_frame() (fling, decay, and spring-back) on a copy of the
+    /// current state, so this can be used to prefetch along the path of a fling without
+    /// affecting it
+    pub fn sample_trajectory(&self, steps: usize, interval_ms: f64) -> Vec<AxisVector<f64>> {
+        self.validator.queried("sample_trajectory()");
+
+        let xs = self.x.predict(steps, interval_ms);
+        let ys = self.y.predict(steps, interval_ms);
+
+        xs.into_iter().zip(ys).map(|(x, y)| {
+            AxisVector {
+                x,
+                y,
+                ..Default::default()
+            }
+        }).collect()
+    }
+
     /// Create a new scrollview with default settings
     ///
     /// Warning: these settings are unlikely to be
@@ -341,15 +1088,68 @@ impl Scrollview {
 
         Scrollview {
             input_per_frame_log: circular_backqueue::ForgetfulLogQueue::new(SAMPLE_OVER_X_FRAMES),
+            edge_hooks: HashMap::new(),
+            overscroll_triggers: Vec::new(),
+            triggered: Vec::new(),
+            on_settle: None,
+            on_scroll_begin: None,
+            on_scroll_end: None,
+            was_animating: false,
+            validator: validate::Validator::new(),
+            clock: Box::new(SystemClock),
             content_height: 0.0,
             content_width: 0.0,
             viewport_height: 0.0,
             viewport_width: 0.0,
+            inset_top: 0.0,
+            inset_bottom: 0.0,
+            inset_left: 0.0,
+            inset_right: 0.0,
             current_source: Source::Undefined,
+            drag_sensitivity: 1.0,
+            pan_started_at: None,
+            initial_pan_ramp_ms: 0.0,
+            hold_since: None,
+            hold_drift: AxisVector::default(),
+            hold_duration_ms: 500.0,
+            last_step_time: None,
+            frame_position: None,
+            frame_velocity: None,
+            frame_acceleration: AxisVector::default(),
+            pan_direction: ScrollDirection::default(),
+            axis_lock: AxisLockMode::default(),
+            claimed_axis: None,
+            input_filters: Vec::new(),
+            last_pan_time: (None, None),
+            outlier_factor: None,
+            recent_deltas: (VecDeque::new(), VecDeque::new()),
+            outlier_dropped: (false, false),
+            mixed_input: MixedInputPolicy::default(),
+            wheel_input: false,
+            frame_source_movement: (0.0, 0.0),
+            rng: SeededRng::new(0),
+            velocity_tracker: None,
+            pending_input_stats: InputStats::default(),
+            input_stats: InputStats::default(),
+            scale_factor: 1.0,
+            scroll_anchor: None,
+            pressure_sensitivity: 0.0,
+            wheel_precision_factor: 1.0,
+            wheel_fast_factor: 1.0,
+            gesture_movement: AxisVector::default(),
+            last_gesture_kind: None,
+            pointers: HashSet::new(),
+            pointer_position: None,
+            zoom_pan_momentum: false,
+            zoom_lockout_ms: 0.0,
+            zoom_ended_at: None,
             dbg_amt_y: 0.0,
             dbg_amt_x: 0.0,
-            //frametime: 0.0,
-            //time_to_pageflip: 0.0,
+            time_to_pageflip: None,
+            auto_prediction: false,
+            frametime: 0.0,
+            last_frame_time: None,
+            frame_marked: false,
             //current_timestamp: 0,
             //interpolation_ratio: 0.0,
             x: Interpolator::new(false, (0.0, 0.0), 0.0),
@@ -368,7 +1168,9 @@ impl Scrollview {
 
     /// Set the geometry for the given scrollview
     ///
-    /// Can be used both on scrollview initialization and on scrollview resize
+    /// Can be used both on scrollview initialization and on scrollview resize. A viewport
+    /// larger than the content leaves that axis pinned at its start and not scrollable (aside
+    /// from any insets), see geometry_warnings() for spotting sizes that look wrong
     pub fn set_geometry(
         &mut self,
         content_height: f64,
@@ -376,113 +1178,3627 @@ impl Scrollview {
         viewport_height: f64,
         viewport_width: f64,
     ) {
+        if !finite_input("set_geometry()", &[content_height, content_width, viewport_height, viewport_width]) {
+            return;
+        }
+
+        self.validator.geometry_set();
+
         self.content_height = content_height;
         self.content_width = content_width;
         self.viewport_height = viewport_height;
         self.viewport_width = viewport_width;
 
-        self.x.set_geometry(0.0, (content_width - viewport_width) as f64);
-        self.y.set_geometry(0.0, (content_height - viewport_height) as f64);
+        self.update_bounds();
     }
 
-    /// True if scrollview should continue to be polled
-    /// even in absence of events (fling or other 
-    /// animation in progress)
-    pub fn animating(&self) -> bool {
-        //println!("Animating called to check in");
-
-        /*let mut file = File::open("/home/sawyer/ctl1.txt").unwrap();
-        let mut contents = String::new();
-        file.read_to_string(&mut contents).unwrap();*/
-        //println!("contents are {}", contents);
-        //let b: f64 = contents.parse().unwrap();
-        //println!("interprets {}", b);
-
-        //return b != 0.0;
-        //return contents.len() > 0;
-        //
-        //self.current_velocity.decay_active()
-        self.x.animating() || self.y.animating()
+    /// Gives anything about the current geometry that looks like misconfiguration, horizontal
+    /// axis first, or nothing if it all looks sensible
+    pub fn geometry_warnings(&self) -> Vec<GeometryWarning> {
+        let axes = [
+            (Axis::Horizontal, self.content_width, self.viewport_width),
+            (Axis::Vertical, self.content_height, self.viewport_height),
+        ];
 
-        //true
-    }
-
-    /// Enqueue a pan event for the referenced scrollview
-    pub fn push_pan(&mut self, axis: Axis, amount: f64, timestamp: Option<u64>) {
-        //println!("push_pan with {:?}, {}, {}", axis, amount, timestamp.unwrap());
-        if !DEBUG {
-            match axis {
-                Axis::Horizontal => self.x.signal_pan(timestamp.unwrap() as f64, amount),
-                Axis::Vertical => self.y.signal_pan(timestamp.unwrap() as f64, amount),
+        let mut warnings = Vec::new();
+        for (axis, content, viewport) in axes {
+            if content < 0.0 || viewport < 0.0 {
+                warnings.push(GeometryWarning::NegativeSize(axis));
             }
-        } else {
-            match axis {
-                Axis::Horizontal => self.dbg_amt_x += amount,
-                Axis::Vertical => self.dbg_amt_y += amount,
+            if content == 0.0 {
+                warnings.push(GeometryWarning::EmptyContent(axis));
+            }
+            if viewport == 0.0 {
+                warnings.push(GeometryWarning::EmptyViewport(axis));
+            }
+            if viewport > content {
+                warnings.push(GeometryWarning::ViewportExceedsContent(axis));
             }
         }
-    }
 
-    /// Enqueue a fling (finger lift at any velocity) for the referenced scrollview
-    pub fn push_fling(&mut self, timestamp: Option<u64>) {
-        eprintln!("Updating config...");
-        Self::update_config();
-        println!("push_fling with {}", timestamp.unwrap());
-        //self.current_velocity.decay_start();
-        self.x.signal_fling(timestamp.unwrap() as f64);
-        self.y.signal_fling(timestamp.unwrap() as f64);
+        warnings
     }
 
-    /// Enqueue a scroll interrupt (finger down at any time, gesture start) for the referenced
-    /// scrollview
+    /// Change the content size while keeping the same piece of content in view, as when a
+    /// document reflows after a font size change
+    ///
+    /// The content point `anchor_fraction` of the way across the viewport (0 for the top/left
+    /// edge, 0.5 for the middle) is taken as a fraction of the old content size, and the
+    /// position is moved so that the same fraction of the new content lands there again. Like
+    /// adjust_position(), any fling in progress carries on from the new position
+    pub fn set_content_size_anchored(&mut self, content_height: f64, content_width: f64, anchor_fraction: f64) {
+        if !finite_input("set_content_size_anchored()", &[content_height, content_width, anchor_fraction]) {
+            return;
+        }
+
+        let position = self.get_position_absolute();
+        let rescale = |position: f64, viewport: f64, old: f64, new: f64| {
+            if old <= 0.0 {
+                return 0.0;
+            }
+
+            let anchor = position + viewport * anchor_fraction;
+            anchor * (new / old) - anchor
+        };
+
+        let delta = AxisVector {
+            x: rescale(position.x, self.viewport_width, self.content_width, content_width),
+            y: rescale(position.y, self.viewport_height, self.content_height, content_height),
+            ..Default::default()
+        };
+
+        self.set_geometry(content_height, content_width, self.viewport_height, self.viewport_width);
+        self.adjust_position(delta);
+    }
+
+    /// Typed version of set_geometry(), taking lengths in either Px or Dip
+    pub fn set_geometry_units<L: Length>(
+        &mut self,
+        content_height: L,
+        content_width: L,
+        viewport_height: L,
+        viewport_width: L,
+    ) {
+        let scale = self.scale_factor;
+        self.set_geometry(
+            content_height.as_px(scale),
+            content_width.as_px(scale),
+            viewport_height.as_px(scale),
+            viewport_width.as_px(scale));
+    }
+
+    /// Set the number of physical pixels per device independent pixel, used to convert Dip
+    /// lengths. Defaults to 1.0
+    ///
+    /// Lengths already given aren't converted again, so set this before the geometry
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        if !finite_input("set_scale_factor()", &[scale_factor]) || scale_factor <= 0.0 {
+            return;
+        }
+
+        self.scale_factor = scale_factor;
+    }
+
+    /// Set how much of each side of the viewport is covered (by a sticky header, a safe area
+    /// inset, etc) and so shouldn't count as showing content
+    ///
+    /// The bounds are extended by each inset, so that at the top bound the start of the content
+    /// sits just below a top inset rather than hidden behind it, and likewise for the other
+    /// sides. Rubber-banding and clamping happen relative to the extended bounds
+    pub fn set_content_insets(&mut self, top: f64, bottom: f64, left: f64, right: f64) {
+        if !finite_input("set_content_insets()", &[top, bottom, left, right]) {
+            return;
+        }
+
+        self.inset_top = top;
+        self.inset_bottom = bottom;
+        self.inset_left = left;
+        self.inset_right = right;
+
+        self.update_bounds();
+    }
+
+    /// Gives every form of the position along the given axis at once, as of the most recent
+    /// step_frame()
+    pub fn position_report(&self, axis: Axis) -> PositionReport {
+        let track = match axis {
+            Axis::Horizontal => &self.x,
+            Axis::Vertical => &self.y,
+        };
+
+        let predicted = track.displayed_position();
+        let (lower, upper) = track.bounds();
+        let fraction = if upper > lower {
+            ((predicted - lower) / (upper - lower)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        PositionReport {
+            predicted,
+            tracked: track.tracked_position(),
+            fraction,
+            overscroll: track.overscroll(),
+            cell: track.current_cell(),
+        }
+    }
+
+    /// Gives the distance content can be scrolled along the given axis between its bounds,
+    /// accounting for insets
+    pub fn scroll_extent(&self, axis: Axis) -> f64 {
+        let (lower, upper) = match axis {
+            Axis::Horizontal => self.x.bounds(),
+            Axis::Vertical => self.y.bounds(),
+        };
+
+        (upper - lower).max(0.0)
+    }
+
+    /// Gives the lowest and highest positions content can reach along the given axis,
+    /// including how far it may be pulled or flung past either bound
+    ///
+    /// For sizing backgrounds and parallax layers that have to cover the content wherever it
+    /// is drawn. Only touchscreens and touchpads rubber-band, so for any other source (and on
+    /// an axis that can't scroll, with set_empty_overscroll() off) this is just the bounds.
+    /// Otherwise overscroll is unbounded unless capped with set_max_overscroll(), making the
+    /// range infinite
+    pub fn position_range(&self, axis: Axis) -> (f64, f64) {
+        match axis {
+            Axis::Horizontal => self.x.position_range(),
+            Axis::Vertical => self.y.position_range(),
+        }
+    }
+
+    /// True if scrollview should continue to be polled
+    /// even in absence of events (fling or other 
+    /// animation in progress)
+    pub fn animating(&self) -> bool {
+        //println!("Animating called to check in");
+
+        /*let mut file = File::open("/home/sawyer/ctl1.txt").unwrap();
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).unwrap();*/
+        //println!("contents are {}", contents);
+        //let b: f64 = contents.parse().unwrap();
+        //println!("interprets {}", b);
+
+        //return b != 0.0;
+        //return contents.len() > 0;
+        //
+        //self.current_velocity.decay_active()
+        self.x.animating() || self.y.animating()
+
+        //true
+    }
+
+    /// Enqueue a pan event for the referenced scrollview
+    ///
+    /// Reports how much of the pan the scrollview could make use of, so that a host arbitrating
+    /// between gesture consumers can pass any remainder on. The pan is queued either way
+    pub fn push_pan(&mut self, axis: Axis, amount: f64, timestamp: Option<u64>) -> PanOutcome {
+        let time = self.event_time(timestamp);
+        self.push_pan_at(axis, amount, time)
+    }
+
+    /// push_pan() with a timestamp of any Timestamp type, such as an Instant or a Duration
+    pub fn push_pan_at<T: Timestamp>(&mut self, axis: Axis, amount: f64, timestamp: T) -> PanOutcome {
+        //println!("push_pan with {:?}, {}, {}", axis, amount, timestamp.unwrap());
+        let time = timestamp.as_millis();
+
+        match axis {
+            Axis::Horizontal => self.x.set_axis_share(1.0),
+            Axis::Vertical => self.y.set_axis_share(1.0),
+        }
+
+        self.pan_at(axis, amount, time)
+    }
+
+    /// push_pan() with the time already worked out, so that pans on both axes of one event
+    /// land at exactly the same time
+    fn pan_at(&mut self, axis: Axis, amount: f64, time: f64) -> PanOutcome {
+        self.pending_input_stats.received += 1;
+
+        if !finite_input("push_pan()", &[amount, time]) {
+            self.pending_input_stats.dropped += 1;
+            return PanOutcome::Rejected;
+        }
+
+        let direction = match self.pan_direction {
+            ScrollDirection::Position => 1.0,
+            ScrollDirection::Content => -1.0,
+        };
+        let amount = amount * direction;
+
+        self.validator.event(time, "push_pan()");
+        self.track_hold(axis, amount, time);
+
+        if self.zoom_locked_out(time) {
+            self.pending_input_stats.dropped += 1;
+            return PanOutcome::Rejected;
+        }
+
+        if self.is_outlier(axis, amount) {
+            self.pending_input_stats.dropped += 1;
+            return PanOutcome::Rejected;
+        }
+
+        let (own, other) = match self.wheel_input {
+            false => self.frame_source_movement,
+            true => (self.frame_source_movement.1, self.frame_source_movement.0),
+        };
+        if self.mixed_input == MixedInputPolicy::Separate && other > 0.0 {
+            self.pending_input_stats.dropped += 1;
+            return PanOutcome::Rejected;
+        }
+
+        let axis = match (self.axis_lock, self.claimed_axis) {
+            (AxisLockMode::Free, _) => axis,
+            (AxisLockMode::Exclusive, Some(claimed)) => claimed,
+            (AxisLockMode::Exclusive, None) => {
+                if amount != 0.0 {
+                    self.claimed_axis = Some(axis);
+                }
+                axis
+            },
+        };
+
+        let previous = match axis {
+            Axis::Horizontal => self.last_pan_time.0,
+            Axis::Vertical => self.last_pan_time.1,
+        };
+        if previous.is_some_and(|previous| time <= previous) {
+            self.pending_input_stats.coalesced += 1;
+        }
+
+        let amount = self.apply_input_filters(axis, amount, time);
+        if !finite_input("push_pan() after input filters", &[amount]) {
+            self.pending_input_stats.dropped += 1;
+            return PanOutcome::Rejected;
+        }
+
+        // eases in the first movement of a gesture, so a large coalesced delta at touch-down
+        // doesn't jerk the content before tracking settles
+        let started = *self.pan_started_at.get_or_insert(time);
+        let ramp = if self.initial_pan_ramp_ms > 0.0 {
+            ((time - started) / self.initial_pan_ramp_ms).clamp(INITIAL_PAN_RAMP_FRACTION, 1.0)
+        } else {
+            1.0
+        };
+
+        // scaled before anything else sees it, so velocity estimation agrees with position
+        let scale = self.drag_sensitivity * ramp;
+        let amount = amount * scale;
+
+        let outcome = match axis {
+            Axis::Horizontal => self.x.pan_outcome(amount),
+            Axis::Vertical => self.y.pan_outcome(amount),
+        };
+
+        match axis {
+            Axis::Horizontal => self.gesture_movement.x += amount.abs(),
+            Axis::Vertical => self.gesture_movement.y += amount.abs(),
+        }
+
+        match self.wheel_input {
+            false => self.frame_source_movement.0 += amount.abs(),
+            true => self.frame_source_movement.1 += amount.abs(),
+        }
+
+        // outweighed this frame, so moved without leaving anything in the history velocity is
+        // measured from
+        if self.mixed_input == MixedInputPolicy::DominantVelocity && other > own + amount.abs() {
+            if !DEBUG {
+                match axis {
+                    Axis::Horizontal => self.x.offset(amount),
+                    Axis::Vertical => self.y.offset(amount),
+                }
+            }
+
+            return Self::unscale_outcome(outcome, scale * direction);
+        }
+
+        if let Some(tracker) = self.velocity_tracker.as_mut() {
+            tracker.add_sample(axis, time, amount);
+        }
+
+        if !DEBUG {
+            match axis {
+                Axis::Horizontal => self.x.signal_pan(time, amount),
+                Axis::Vertical => self.y.signal_pan(time, amount),
+            }
+        } else {
+            match axis {
+                Axis::Horizontal => self.dbg_amt_x += amount,
+                Axis::Vertical => self.dbg_amt_y += amount,
+            }
+        }
+
+        // report the remainder in the caller's own units, and the right way round
+        Self::unscale_outcome(outcome, scale * direction)
+    }
+
+    /// Set which way round the deltas given to push_pan(), push_wheel() and the other pan
+    /// methods are, see ScrollDirection. Remainders in PanOutcome come back the same way round
+    ///
+    /// Positions and adjust_position() always work in position terms. Defaults to Position,
+    /// which is how deltas have always been taken
+    pub fn set_pan_direction(&mut self, direction: ScrollDirection) {
+        self.pan_direction = direction;
+    }
+
+    /// Set how pans on one axis affect the other, see AxisLockMode. Changing the mode frees
+    /// any axis already claimed. Defaults to Free
+    ///
+    /// With Exclusive, a pan pushed for the other axis is applied as-is to the claimed one, so
+    /// a diagonal drag moves the claimed axis by both its components
+    pub fn set_axis_lock(&mut self, mode: AxisLockMode) {
+        self.axis_lock = mode;
+        self.claimed_axis = None;
+    }
+
+    /// Have release velocity measured by the given tracker, rather than from the pan history
+    /// kept for interpolation
+    ///
+    /// The tracker's estimate is taken as the raw speed of the input, so it's still accelerated
+    /// (for sources that accelerate) and boosted as usual once the fling starts. A release after
+    /// holding still flings nowhere, whatever the tracker says
+    pub fn set_velocity_tracker(&mut self, tracker: Box<dyn VelocityTracker>) {
+        self.velocity_tracker = Some(tracker);
+    }
+
+    /// Go back to measuring release velocity from the pan history
+    pub fn clear_velocity_tracker(&mut self) {
+        self.velocity_tracker = None;
+    }
+
+    /// Drop any single pan more than factor times the size of the few before it on its axis,
+    /// as a stray jump from a palm or accidental touch would be, or turn this off with None
+    ///
+    /// A dropped pan moves nothing and plays no part in velocity, and is counted as dropped by
+    /// input_stats(). Only one pan in a row is dropped, so a real sudden speed-up gets through
+    /// on its next delta. The first few pans of each gesture have nothing to be judged against,
+    /// so are always let through. Defaults to None
+    pub fn set_outlier_rejection(&mut self, factor: Option<f64>) {
+        self.outlier_factor = factor.filter(|factor| factor.is_finite() && *factor > 0.0);
+        self.forget_recent_deltas();
+    }
+
+    /// Add a filter to the end of the pipeline every pan delta passes through before anything
+    /// else, including drag sensitivity, sees it
+    ///
+    /// Remainders reported by push_pan() are in the units the filters produce
+    pub fn push_input_filter(&mut self, filter: Box<dyn InputFilter>) {
+        self.input_filters.push(filter);
+    }
+
+    /// Remove every filter added with push_input_filter()
+    pub fn clear_input_filters(&mut self) {
+        self.input_filters.clear();
+    }
+
+    /// Typed version of push_pan(), taking the delta in either Px or Dip
+    pub fn push_pan_units<L: Length>(&mut self, axis: Axis, amount: L, timestamp: Option<u64>) -> PanOutcome {
+        let time = self.event_time(timestamp);
+        self.push_pan_units_at(axis, amount, time)
+    }
+
+    /// push_pan_units() with a timestamp of any Timestamp type, such as an Instant or a Duration
+    pub fn push_pan_units_at<L: Length, T: Timestamp>(&mut self, axis: Axis, amount: L, timestamp: T) -> PanOutcome {
+        self.push_pan_at(axis, amount.as_px(self.scale_factor), timestamp)
+    }
+
+    /// Enqueue a pan event from one of several pointers (fingers) that are down at once
+    ///
+    /// Content tracks the centroid of all pointers currently down, so each pointer's movement
+    /// counts for 1/n of the pan. Pointers moving apart or together (a pinch) cancel out and
+    /// don't pan. A pointer counts as down from its first event until push_pointer_lift(),
+    /// push_fling() or push_interrupt(), and since only movement is tracked, lifting one leaves the centroid where
+    /// it was, so switching down to single-finger panning doesn't jump.
+    ///
+    /// Only the pan is handled here. Scrollviews don't model zoom, so the spread between
+    /// pointers isn't tracked and scale is left to the host, which reports the end of a pinch
+    /// through push_zoom_end()
+    pub fn push_pan_multi(&mut self, pointer_id: u64, axis: Axis, amount: f64, timestamp: Option<u64>) -> PanOutcome {
+        let time = self.event_time(timestamp);
+        self.push_pan_multi_at(pointer_id, axis, amount, time)
+    }
+
+    /// push_pan_multi() with a timestamp of any Timestamp type, such as an Instant or a Duration
+    pub fn push_pan_multi_at<T: Timestamp>(&mut self, pointer_id: u64, axis: Axis, amount: f64, timestamp: T) -> PanOutcome {
+        self.pointers.insert(pointer_id);
+
+        self.push_pan_at(axis, amount / self.pointers.len() as f64, timestamp)
+    }
+
+    /// Enqueue a pan event that carries the pressure it was made with, for styluses and
+    /// force-sensitive touchpads
+    ///
+    /// Pressure is expected from 0 (lightest) to 1 (firmest), and is clamped to that range.
+    /// The delta is scaled by `1 + pressure * sensitivity`, with the sensitivity given by
+    /// set_pressure_sensitivity(), so with the default of 0 this is the same as push_pan()
+    pub fn push_pan_pressure(&mut self, axis: Axis, amount: f64, pressure: f64, timestamp: Option<u64>) -> PanOutcome {
+        let time = self.event_time(timestamp);
+        self.push_pan_pressure_at(axis, amount, pressure, time)
+    }
+
+    /// push_pan_pressure() with a timestamp of any Timestamp type, such as an Instant or a Duration
+    pub fn push_pan_pressure_at<T: Timestamp>(&mut self, axis: Axis, amount: f64, pressure: f64, timestamp: T) -> PanOutcome {
+        if !finite_input("push_pan_pressure()", &[pressure]) {
+            return PanOutcome::Rejected;
+        }
+
+        let scale = (1.0 + pressure.clamp(0.0, 1.0) * self.pressure_sensitivity).max(0.0);
+
+        // remainder goes back out in the caller's own units, as with drag_sensitivity
+        Self::unscale_outcome(self.push_pan_at(axis, amount * scale, timestamp), scale)
+    }
+
+    /// Enqueue a wheel event along with any modifier held while it was made
+    ///
+    /// The delta is scaled by the factor set for the modifier, so one detent moves further or
+    /// less far, and is otherwise handled exactly as push_pan()
+    pub fn push_wheel(&mut self, axis: Axis, amount: f64, modifier: WheelModifier, timestamp: Option<u64>) -> PanOutcome {
+        let time = self.event_time(timestamp);
+        self.push_wheel_at(axis, amount, modifier, time)
+    }
+
+    /// push_wheel() with a timestamp of any Timestamp type, such as an Instant or a Duration
+    pub fn push_wheel_at<T: Timestamp>(&mut self, axis: Axis, amount: f64, modifier: WheelModifier, timestamp: T) -> PanOutcome {
+        let scale = self.wheel_scale(modifier);
+
+        self.wheel_input = true;
+        let outcome = self.push_pan_at(axis, amount * scale, timestamp);
+        self.wheel_input = false;
+
+        Self::unscale_outcome(outcome, scale)
+    }
+
+    /// Enqueue a diagonal wheel event that moved both axes at once, as some trackpads report
+    ///
+    /// Both deltas are applied at the same instant, and acceleration is worked out from the
+    /// speed along the diagonal rather than each axis alone, so the movement, and the velocity
+    /// a fling after a run of these starts with, keep the direction of the input. Gives the
+    /// outcome on the horizontal axis, then the vertical one
+    pub fn push_wheel_2d(&mut self, dx: f64, dy: f64, modifier: WheelModifier, timestamp: Option<u64>) -> (PanOutcome, PanOutcome) {
+        let time = self.event_time(timestamp);
+        self.push_wheel_2d_at(dx, dy, modifier, time)
+    }
+
+    /// push_wheel_2d() with a timestamp of any Timestamp type, such as an Instant or a Duration
+    pub fn push_wheel_2d_at<T: Timestamp>(&mut self, dx: f64, dy: f64, modifier: WheelModifier, timestamp: T) -> (PanOutcome, PanOutcome) {
+        let time = timestamp.as_millis();
+        let scale = self.wheel_scale(modifier);
+
+        self.set_axis_shares(dx, dy);
+
+        self.wheel_input = true;
+        let outcomes = (
+            Self::unscale_outcome(self.pan_at(Axis::Horizontal, dx * scale, time), scale),
+            Self::unscale_outcome(self.pan_at(Axis::Vertical, dy * scale, time), scale),
+        );
+        self.wheel_input = false;
+
+        outcomes
+    }
+
+    /// Enqueue a pan given as where the pointer now is on screen, in px, rather than how far
+    /// it moved
+    ///
+    /// Movement is measured from the previous pointer position since the last interrupt, fling
+    /// or release, so the first one after those only records where the pointer started and
+    /// moves nothing (giving Consumed). Since each delta is taken between positions, rounding in
+    /// them can't build up into drift the way it can when the platform's own deltas are added
+    /// up. Content follows the pointer, as in a drag, whatever set_pan_direction() says, and
+    /// remainders come back as pointer movement. Like push_wheel_2d(), both axes move at the
+    /// same instant and accelerate along the direction of movement
+    pub fn push_pointer(&mut self, x: f64, y: f64, timestamp: Option<u64>) -> (PanOutcome, PanOutcome) {
+        let time = self.event_time(timestamp);
+        self.push_pointer_at(x, y, time)
+    }
+
+    /// push_pointer() with a timestamp of any Timestamp type, such as an Instant or a Duration
+    pub fn push_pointer_at<T: Timestamp>(&mut self, x: f64, y: f64, timestamp: T) -> (PanOutcome, PanOutcome) {
+        let time = timestamp.as_millis();
+        if !finite_input("push_pointer()", &[x, y, time]) {
+            return (PanOutcome::Rejected, PanOutcome::Rejected);
+        }
+
+        let (last_x, last_y) = match self.pointer_position.replace((x, y)) {
+            Some(last) => last,
+            None => return (PanOutcome::Consumed, PanOutcome::Consumed),
+        };
+
+        // moving the pointer moves the content, so given as content movement whichever way
+        // pan_at() takes deltas
+        let direction = match self.pan_direction {
+            ScrollDirection::Position => -1.0,
+            ScrollDirection::Content => 1.0,
+        };
+
+        let (dx, dy) = (x - last_x, y - last_y);
+        self.set_axis_shares(dx, dy);
+
+        (
+            Self::unscale_outcome(self.pan_at(Axis::Horizontal, dx * direction, time), direction),
+            Self::unscale_outcome(self.pan_at(Axis::Vertical, dy * direction, time), direction),
+        )
+    }
+
+    /// Has acceleration on each axis worked out from the speed along the whole movement
+    fn set_axis_shares(&mut self, dx: f64, dy: f64) {
+        // an axis that doesn't move has no speed to accelerate, so its share doesn't matter
+        let length = dx.hypot(dy);
+        let share = |d: f64| if d != 0.0 && length.is_finite() { d.abs() / length } else { 1.0 };
+        self.x.set_axis_share(share(dx));
+        self.y.set_axis_share(share(dy));
+    }
+
+    fn wheel_scale(&self, modifier: WheelModifier) -> f64 {
+        match modifier {
+            WheelModifier::None => 1.0,
+            WheelModifier::Precision => self.wheel_precision_factor,
+            WheelModifier::Fast => self.wheel_fast_factor,
+        }
+    }
+
+    /// Reports a remainder in the units of a delta from before it was scaled
+    fn unscale_outcome(outcome: PanOutcome, scale: f64) -> PanOutcome {
+        match outcome {
+            PanOutcome::PartiallyConsumed { remaining } if scale != 0.0 => {
+                PanOutcome::PartiallyConsumed { remaining: remaining / scale }
+            },
+            other => other,
+        }
+    }
+
+    /// Set how pans and wheel events pushed before the same step_frame() are combined, see
+    /// MixedInputPolicy. Wheel events are those given to push_wheel() and push_wheel_2d(), and
+    /// everything else counts as a pan. Defaults to Merge
+    pub fn set_mixed_input_policy(&mut self, policy: MixedInputPolicy) {
+        self.mixed_input = policy;
+    }
+
+    /// Set the scale applied to wheel deltas pushed with WheelModifier::Precision, usually
+    /// below 1 for finer steps. Defaults to 1.0
+    pub fn set_wheel_precision_factor(&mut self, factor: f64) {
+        if !finite_input("set_wheel_precision_factor()", &[factor]) {
+            return;
+        }
+
+        self.wheel_precision_factor = factor;
+    }
+
+    /// Set the scale applied to wheel deltas pushed with WheelModifier::Fast, usually large
+    /// enough that a detent moves about a page. Defaults to 1.0
+    pub fn set_wheel_fast_factor(&mut self, factor: f64) {
+        if !finite_input("set_wheel_fast_factor()", &[factor]) {
+            return;
+        }
+
+        self.wheel_fast_factor = factor;
+    }
+
+    /// Ease in the first pans of each gesture over duration_ms, starting from a quarter of their
+    /// delta at the first pan and reaching the full delta by the end
+    ///
+    /// Guards against the content leaping at touch-down when the platform coalesces a large
+    /// initial delta. A gesture starts at the first pan after an interrupt, fling or release.
+    /// Defaults to 0, which turns the ramp off
+    pub fn set_initial_pan_ramp(&mut self, duration_ms: f64) {
+        if !finite_input("set_initial_pan_ramp()", &[duration_ms]) {
+            return;
+        }
+
+        self.initial_pan_ramp_ms = duration_ms.max(0.0);
+    }
+
+    /// Set how strongly pressure passed to push_pan_pressure() scales pan deltas
+    ///
+    /// At full pressure a pan moves `1 + sensitivity` times as far. Negative values make firm
+    /// presses move less, down to not at all. Defaults to 0, ignoring pressure
+    pub fn set_pressure_sensitivity(&mut self, sensitivity: f64) {
+        if !finite_input("set_pressure_sensitivity()", &[sensitivity]) {
+            return;
+        }
+
+        self.pressure_sensitivity = sensitivity;
+    }
+
+    /// Signal that one of the pointers reporting through push_pan_multi() has lifted, while
+    /// others remain down
+    pub fn push_pointer_lift(&mut self, pointer_id: u64) {
+        self.pointers.remove(&pointer_id);
+    }
+
+    /// Bring any fling in progress to a stop over the next decel_ms ms, slowing steeply but
+    /// smoothly rather than stopping dead as push_interrupt() does
+    ///
+    /// Measured from the most recent step_frame(). A fling overscrolled at the time still
+    /// springs back in first. Pushing new input cancels the arrest along with the fling
+    pub fn arrest_fling(&mut self, decel_ms: f64) {
+        if !finite_input("arrest_fling()", &[decel_ms]) {
+            return;
+        }
+
+        let time = self.last_step_time.unwrap_or_else(|| self.clock.now());
+        self.x.arrest(time, decel_ms.max(0.0));
+        self.y.arrest(time, decel_ms.max(0.0));
+    }
+
+    /// Set how long, in ms, a fling takes to ease back in when its axis stops being scrollable
+    /// partway through. Defaults to 300
+    ///
+    /// Content shrinking to fit the viewport (or the viewport growing to fit it) mid-fling
+    /// leaves the fling nowhere to go. Rather than carrying on overscrolled and slamming back
+    /// on the spring, it's dropped and the content eases over to the start of the track, the
+    /// only position left, over this long. 0 puts it there at the next step_frame()
+    pub fn set_collapse_duration(&mut self, ms: f64) {
+        if !finite_input("set_collapse_duration()", &[ms]) {
+            return;
+        }
+
+        self.x.set_collapse_duration(ms.max(0.0));
+        self.y.set_collapse_duration(ms.max(0.0));
+    }
+
+    /// Gives how far through its decay the fling in progress is, from 0 as it starts to 1 as it
+    /// stops, or None if not flinging
+    ///
+    /// Progress is measured by speed against the speed the fling started at, on whichever axis
+    /// started faster, so it suits driving a progress bar or an animation coupled to momentum
+    pub fn fling_progress(&self) -> Option<f64> {
+        let (x, y) = (self.x.fling_progress(), self.y.fling_progress());
+
+        match (x, y) {
+            (Some(_), Some(_)) if self.x.fling_start_speed() > self.y.fling_start_speed() => x,
+            (Some(_), Some(_)) => y,
+            _ => x.or(y),
+        }
+    }
+
+    /// Scrub the fling in progress to the given point in its decay, see fling_progress()
+    ///
+    /// Sets the fling's speed to what it was (or would be) at that progress, so moving back
+    /// toward 0 gives it back momentum and moving toward 1 takes it away. Position isn't rewound,
+    /// the fling carries on from where it is. Does nothing if not flinging
+    pub fn set_fling_progress(&mut self, progress: f64) {
+        if !finite_input("set_fling_progress()", &[progress]) {
+            return;
+        }
+
+        self.x.set_fling_progress(progress);
+        self.y.set_fling_progress(progress);
+    }
+
+    /// Give an axis a pan history moving at velocity (px/ms), so a push_fling() straight after
+    /// flings on at that speed, as when taking over from momentum the platform was running
+    ///
+    /// The history ends at the time of the most recent step_frame() (or the clock, if there
+    /// hasn't been one), so fling at that time or shortly after, before it's taken as the pan
+    /// having stopped. The velocity is treated as input, and so accelerated for sources that
+    /// accelerate. Anything in progress on the axis is replaced
+    pub fn seed_velocity_samples(&mut self, axis: Axis, velocity: f64) {
+        if !finite_input("seed_velocity_samples()", &[velocity]) {
+            return;
+        }
+
+        let time = self.last_step_time.unwrap_or_else(|| self.clock.now());
+        match axis {
+            Axis::Horizontal => self.x.seed_velocity(time, velocity),
+            Axis::Vertical => self.y.seed_velocity(time, velocity),
+        }
+    }
+
+    /// Enqueue a fling (finger lift) for the referenced scrollview
+    ///
+    /// A release with no real velocity behind it, as after dragging and then holding still,
+    /// doesn't fling on whatever was left over from before the pause. Like push_release(), an
+    /// axis moving slower than `min_fling_velocity` (px/ms) settles instead, springing in to
+    /// the nearest bound or snap point if it isn't already on one
+    pub fn push_fling(&mut self, timestamp: Option<u64>) {
+        let time = self.event_time(timestamp);
+        self.push_fling_at(time)
+    }
+
+    /// push_fling() with a timestamp of any Timestamp type, such as an Instant or a Duration
+    pub fn push_fling_at<T: Timestamp>(&mut self, timestamp: T) {
+        let time = timestamp.as_millis();
+        if !finite_input("push_fling()", &[time]) {
+            return;
+        }
+
+        self.validator.event(time, "push_fling()");
+        self.pointers.clear();
+        self.pointer_position = None;
+        self.forget_recent_deltas();
+        self.pan_started_at = None;
+        self.claimed_axis = None;
+        self.hold_since = None;
+
+        // whatever movement made it through is residue from the zoom, so stop rather than fling
+        if self.zoom_locked_out(time) {
+            self.last_gesture_kind = None;
+            if !self.zoom_pan_momentum {
+                self.x.signal_interrupt(time);
+                self.y.signal_interrupt(time);
+            }
+            return;
+        }
+
+        eprintln!("Updating config...");
+        Self::update_config();
+        println!("push_fling with {}", time);
+        //self.current_velocity.decay_start();
+        let min_velocity = self.y.config().MIN_FLING_VELOCITY;
+        self.apply_velocity_tracker();
+        let flung_x = self.x.signal_release(time, min_velocity);
+        let flung_y = self.y.signal_release(time, min_velocity);
+        self.classify_gesture(flung_x || flung_y);
+    }
+
+    /// Signal that a pinch-zoom gesture, recognized by the host, has just ended
+    ///
+    /// Fingers drifting as they lift off after a pinch would otherwise read as a pan and
+    /// fling. Any pan in progress is dropped, and for `set_post_zoom_fling_lockout_ms()`
+    /// afterward pans are rejected and a fling stops content where it is instead.
+    ///
+    /// With set_zoom_pan_momentum() on, pans in progress are released to fling on instead
+    pub fn push_zoom_end<T: Timestamp>(&mut self, timestamp: T) {
+        let time = timestamp.as_millis();
+        if !finite_input("push_zoom_end()", &[time]) {
+            return;
+        }
+
+        self.validator.event(time, "push_zoom_end()");
+        self.zoom_ended_at = Some(time);
+
+        if self.zoom_pan_momentum {
+            let min_velocity = self.y.config().MIN_FLING_VELOCITY;
+            self.apply_velocity_tracker();
+            self.x.signal_release(time, min_velocity);
+            self.y.signal_release(time, min_velocity);
+        } else {
+            self.x.signal_interrupt(time);
+            self.y.signal_interrupt(time);
+        }
+    }
+
+    /// Set whether the translation of a pinch carries on as a fling once it ends
+    ///
+    /// The host pushes the movement of the pinch's center as pans while it goes on (so content
+    /// follows the fingers), then push_zoom_end(). With this on, those pans are released there
+    /// as push_release() would, so a pinch thrown sideways glides on rather than stopping dead.
+    /// A fling or release pushed during the lockout after it is then ignored, rather than
+    /// stopping the content, since the momentum is meant. Defaults to off
+    pub fn set_zoom_pan_momentum(&mut self, enabled: bool) {
+        self.zoom_pan_momentum = enabled;
+    }
+
+    /// Set how long after push_zoom_end() pans and flings are suppressed, in ms
+    ///
+    /// Defaults to 0, so nothing is suppressed
+    pub fn set_post_zoom_fling_lockout_ms(&mut self, ms: f64) {
+        if !finite_input("set_post_zoom_fling_lockout_ms()", &[ms]) {
+            return;
+        }
+
+        self.zoom_lockout_ms = ms.max(0.0);
+    }
+
+    /// Enqueue the end of a drag (finger lift) for the referenced scrollview, leaving it to the
+    /// scrollview whether that should fling
+    ///
+    /// Each axis flings if it was moving at `min_fling_velocity` (px/ms) or faster at the time
+    /// of release, and otherwise stops where it is, springing back first if overscrolled or
+    /// off its snap grid. This
+    /// is what platforms report as pointer-up, and can be used in place of choosing between
+    /// push_fling() and push_interrupt()
+    pub fn push_release(&mut self, timestamp: Option<u64>) {
+        let time = self.event_time(timestamp);
+        self.push_release_at(time)
+    }
+
+    /// push_release() with a timestamp of any Timestamp type, such as an Instant or a Duration
+    pub fn push_release_at<T: Timestamp>(&mut self, timestamp: T) {
+        let time = timestamp.as_millis();
+        if !finite_input("push_release()", &[time]) {
+            return;
+        }
+
+        self.validator.event(time, "push_release()");
+        self.pointers.clear();
+        self.pointer_position = None;
+        self.forget_recent_deltas();
+        self.pan_started_at = None;
+        self.claimed_axis = None;
+        self.hold_since = None;
+
+        if self.zoom_locked_out(time) {
+            self.last_gesture_kind = None;
+            if !self.zoom_pan_momentum {
+                self.x.signal_interrupt(time);
+                self.y.signal_interrupt(time);
+            }
+            return;
+        }
+
+        let min_velocity = self.y.config().MIN_FLING_VELOCITY;
+        self.apply_velocity_tracker();
+        let flung_x = self.x.signal_release(time, min_velocity);
+        let flung_y = self.y.signal_release(time, min_velocity);
+        self.classify_gesture(flung_x || flung_y);
+    }
+
+    /// Enqueue a scroll interrupt (finger down at any time, gesture start) for the referenced
+    /// scrollview
+    ///
+    /// Content is frozen at the last position produced by step_frame(), rather than wherever the
+    /// fling would have carried it by the time of the interrupt, so it doesn't jump under the
+    /// finger at touch-down
+    ///
+    /// Interrupts repeated while already idle, with no pans pushed since the last one, are
+    /// ignored, so platforms that send several per touch-down are harmless
     pub fn push_interrupt(&mut self, timestamp: Option<u64>) {
+        let time = self.event_time(timestamp);
+        self.push_interrupt_at(time)
+    }
+
+    /// push_interrupt() with a timestamp of any Timestamp type, such as an Instant or a Duration
+    pub fn push_interrupt_at<T: Timestamp>(&mut self, timestamp: T) {
+        let time = timestamp.as_millis();
+        if !finite_input("push_interrupt()", &[time]) {
+            return;
+        }
+
+        self.validator.event(time, "push_interrupt()");
         //self.pan_log_x.clear();
         //self.pan_log_y.clear();
         //self.current_velocity = AxisVector { x: 0.0, y: 0.0, ..self.current_velocity };
-        self.x.signal_interrupt(timestamp.unwrap() as f64);
-        self.y.signal_interrupt(timestamp.unwrap() as f64);
+        self.gesture_movement = AxisVector::default();
+        self.last_pan_time = (None, None);
+        self.pan_started_at = None;
+        self.claimed_axis = None;
+        self.pointers.clear();
+        self.pointer_position = None;
+        self.forget_recent_deltas();
+        if let Some(tracker) = self.velocity_tracker.as_mut() {
+            tracker.clear();
+        }
+        self.hold_since = Some(time);
+        self.hold_drift = AxisVector::default();
+        self.x.signal_interrupt(time);
+        self.y.signal_interrupt(time);
+    }
+
+    /// Whether the pointer is down and has been held roughly still for at least the hold
+    /// duration, as of the most recent step_frame()
+    ///
+    /// The pointer counts as down from push_interrupt() (or the first pan) until push_fling()
+    /// or push_release(). Drifting more than a few px in total restarts the hold. Intended for
+    /// long-press menus and starting drag-and-drop without a separate recognizer
+    pub fn is_holding(&self) -> bool {
+        match (self.hold_since, self.last_step_time) {
+            (Some(since), Some(now)) => now - since >= self.hold_duration_ms,
+            _ => false,
+        }
+    }
+
+    /// Set how long, in ms, the pointer must be held still before is_holding() is true.
+    /// Defaults to 500
+    pub fn set_hold_duration(&mut self, ms: f64) {
+        if !finite_input("set_hold_duration()", &[ms]) {
+            return;
+        }
+
+        self.hold_duration_ms = ms.max(0.0);
+    }
+
+    /// Counts of the pans taken in by the most recent step_frame(), that is those pushed
+    /// between it and the frame before
+    ///
+    /// For telling input starvation (few pans received) apart from tuning problems when
+    /// scrolling feels laggy. A frame with no pans before it gives all zeros
+    pub fn input_stats(&self) -> InputStats {
+        self.input_stats
+    }
+
+    /// How the most recent push_fling() or push_release() ended the gesture before it
+    ///
+    /// Tap if the pans since the last interrupt came to no more than a few px in total, and
+    /// otherwise Fling if either axis was let go fast enough to fling on, or DragStop if not.
+    /// None before the first release, or if the last one only ended a zoom.
+    /// Lets hosts tell a tap from a drag without running a recognizer of their own
+    pub fn last_gesture_kind(&self) -> Option<GestureKind> {
+        self.last_gesture_kind
+    }
+
+    fn classify_gesture(&mut self, flung: bool) {
+        let AxisVector { x, y, .. } = self.gesture_movement;
+
+        // a few px of jitter under a tap can still be quick enough to fling on
+        self.last_gesture_kind = Some(if x.hypot(y) <= HOLD_SLOP_PX {
+            GestureKind::Tap
+        } else if flung {
+            GestureKind::Fling
+        } else {
+            GestureKind::DragStop
+        });
+    }
+
+    /// Gives the axis that has seen the most pan movement since the last interrupt
+    ///
+    /// Answers for as long as the gesture lasts, whether the pointer is down (moving or held
+    /// still) or a fling from it is still going. None once it's let go and come to rest, or if
+    /// both axes have moved equally (including not at all)
+    pub fn dominant_axis(&self) -> Option<Axis> {
+        // the pointer counts as down from the interrupt or first pan until it's let go, see
+        // is_holding()
+        let pointer_down = self.hold_since.is_some();
+        if !pointer_down && !self.animating() {
+            return None;
+        }
+
+        let AxisVector { x, y, .. } = self.gesture_movement;
+        if x > y {
+            Some(Axis::Horizontal)
+        } else if y > x {
+            Some(Axis::Vertical)
+        } else {
+            None
+        }
+    }
+
+    /// Set the model used to decay fling velocity on both axes
+    ///
+    /// Safe to change mid-fling, as when a scroll speed setting is adjusted while content is
+    /// still moving: the fling keeps its current velocity and eases over to the new model's
+    /// deceleration within a few frames, rather than braking harder or softer all at once
+    pub fn set_fling_model(&mut self, model: FlingModel) {
+        self.x.set_fling_model(model);
+        self.y.set_fling_model(model);
+    }
+
+    /// Gives how much the current velocity prediction can be trusted, from 0 to 1, taking the
+    /// more confident of the two axes
+    ///
+    /// Grows with the number of events seen in the current gesture (reaching full weight at
+    /// `confidence_full_event_count`), and shrinks when those events disagree about velocity.
+    /// The prediction lead (`shift_window_ms`) is scaled by this, so prediction eases in over
+    /// the start of a pan rather than snapping on at the second event
+    pub fn prediction_confidence(&self) -> f64 {
+        self.x.prediction_confidence().max(self.y.prediction_confidence())
+    }
+
+    /// Set the average time between frames, in ms, as when the refresh rate changes (the window
+    /// moved to another display, say)
+    ///
+    /// While auto prediction is on this replaces its estimate, and otherwise is where the
+    /// estimate starts from once it's turned on. Pan history, flings, and the lead were all
+    /// measured against the old rate, so everything in progress is flushed: content is left at
+    /// rest wherever the most recent step_frame() showed it, and the next pan starts a fresh
+    /// gesture
+    pub fn set_avg_frametime(&mut self, ms: f64) {
+        if !finite_input("set_avg_frametime()", &[ms]) || ms <= 0.0 {
+            return;
+        }
+
+        self.frametime = ms;
+        self.last_frame_time = None;
+        if self.auto_prediction {
+            self.x.set_lead(Some(ms));
+            self.y.set_lead(Some(ms));
+        }
+
+        let time = self.last_step_time.unwrap_or_else(|| self.clock.now());
+        self.x.reset(time);
+        self.y.reset(time);
+
+        self.gesture_movement = AxisVector::default();
+        self.last_pan_time = (None, None);
+        self.pan_started_at = None;
+        self.claimed_axis = None;
+        self.pointers.clear();
+        self.pointer_position = None;
+        self.forget_recent_deltas();
+        if let Some(tracker) = self.velocity_tracker.as_mut() {
+            tracker.clear();
+        }
+    }
+
+    /// Gives the frametime, in ms, that prediction is currently working from
+    ///
+    /// This is the smoothed estimate while auto prediction is on (0 until two frames have been
+    /// marked), and otherwise the value given to set_next_frame_predict(), or
+    /// `shift_window_ms` if none has been
+    pub fn effective_frametime(&self) -> Millis {
+        if self.auto_prediction {
+            self.frametime
+        } else {
+            self.time_to_pageflip.unwrap_or_else(|| CONFIG.read().unwrap().SHIFT_WINDOW_MS)
+        }
+    }
+
+    /// Gives how far ahead, in ms, the most recent step_frame() actually predicted, taking the
+    /// longer of the two axes
+    ///
+    /// This is effective_frametime() after scaling by prediction_confidence() and the
+    /// velocity rolloff, or 0 on an axis with prediction disabled
+    pub fn effective_lead_time(&self) -> Millis {
+        self.x.lead().max(self.y.lead())
+    }
+
+    /// Quantize where flings come to rest on the given axis to a uniform grid of cells
+    ///
+    /// Cell boundaries lie at `offset + n * cell_size`. Once a fling has slowed below
+    /// `snap_velocity_threshold` it springs in to the nearest boundary, so a stronger fling
+    /// naturally carries across more cells before settling. A `cell_size` of 0 disables snapping
+    pub fn set_grid_snap(&mut self, axis: Axis, cell_size: f64, offset: f64) {
+        if !finite_input("set_grid_snap()", &[cell_size, offset]) {
+            return;
+        }
+
+        match axis {
+            Axis::Horizontal => self.x.set_grid_snap(cell_size, offset),
+            Axis::Vertical => self.y.set_grid_snap(cell_size, offset),
+        }
+    }
+
+    /// Set where flings settle on the given axis when they come to rest past the last whole grid
+    /// cell, see EndBias. Only matters with a grid set through set_grid_snap(), and only when
+    /// the content doesn't divide evenly into cells. Defaults to Nearest
+    pub fn set_end_bias(&mut self, axis: Axis, bias: EndBias) {
+        match axis {
+            Axis::Horizontal => self.x.set_end_bias(bias),
+            Axis::Vertical => self.y.set_end_bias(bias),
+        }
+    }
+
+    /// Draw drags toward grid boundaries as they pass near them, on both axes
+    ///
+    /// Within a quarter of a cell of a boundary the content glides in a little ahead of the
+    /// pointer and then lags it moving away, so landing on a boundary feels deliberate before
+    /// anything is let go. strength runs from 0 (off, the default) to 1, strongest. Only
+    /// applies along axes with a grid set through set_grid_snap(), and only while dragging:
+    /// flings still settle as set_grid_snap() describes
+    pub fn set_snap_magnetism(&mut self, strength: f64) {
+        if !finite_input("set_snap_magnetism()", &[strength]) {
+            return;
+        }
+
+        self.x.set_snap_magnetism(strength);
+        self.y.set_snap_magnetism(strength);
+    }
+
+    /// Limit how quickly the acceleration of spring animations (snapping to the grid, springing
+    /// back from overscroll, and play_peek()) may change, in px/ms³
+    ///
+    /// Without a limit the spring takes hold with its full force at once, which shows as a
+    /// lurch at the start of a snap or at the moment a fling hits a bound. With one,
+    /// acceleration changes continuously and never faster than the limit, from the moment the
+    /// spring comes on until it lands exactly where it's headed. Such a spring closes in without
+    /// ringing, only as quickly as the limit allows, so a tight limit makes for slower snaps and
+    /// deeper overscroll after a fast fling. 0 removes the limit, which is the default
+    pub fn set_jerk_limit(&mut self, limit: f64) {
+        if limit.is_nan() {
+            return;
+        }
+
+        self.x.set_jerk_limit(limit);
+        self.y.set_jerk_limit(limit);
+    }
+
+    /// Gives the index of the grid cell the current position falls in on the given axis,
+    /// or 0 if no grid has been set with set_grid_snap()
+    pub fn current_cell(&self, axis: Axis) -> i64 {
+        match axis {
+            Axis::Horizontal => self.x.current_cell(),
+            Axis::Vertical => self.y.current_cell(),
+        }.unwrap_or(0)
+    }
+
+    /// Shift the content position by delta without disturbing any pan or fling in progress
+    ///
+    /// Intended for keeping visible content still when the layout changes around it, such as
+    /// items being inserted above the viewport in a chat or feed. Velocity is preserved, so an
+    /// active fling carries on from the new position as though it had always been there
+    pub fn adjust_position(&mut self, delta: AxisVector<f64>) {
+        if !finite_input("adjust_position()", &[delta.x, delta.y]) {
+            return;
+        }
+
+        self.x.offset(delta.x);
+        self.y.offset(delta.y);
+    }
+
+    /// Choose an element to keep stationary on screen while content above it changes size, as
+    /// browsers do with scroll anchoring
+    ///
+    /// `element_top` is the element's current top edge in content coordinates. Replaces any
+    /// previous anchor
+    pub fn set_scroll_anchor(&mut self, element_top: f64, element_id: u64) {
+        if !finite_input("set_scroll_anchor()", &[element_top]) {
+            return;
+        }
+
+        self.scroll_anchor = Some((element_id, element_top));
+    }
+
+    /// Report that layout has moved an element's top edge to new_top, in content coordinates
+    ///
+    /// If it's the anchor element, the position is shifted by as much as it moved (as with
+    /// adjust_position()), so it stays where it was in the viewport. Other elements are ignored
+    pub fn notify_anchor_moved(&mut self, element_id: u64, new_top: f64) {
+        if !finite_input("notify_anchor_moved()", &[new_top]) {
+            return;
+        }
+
+        if let Some((id, top)) = self.scroll_anchor {
+            if id == element_id {
+                self.scroll_anchor = Some((id, new_top));
+                self.adjust_position(AxisVector { y: new_top - top, ..Default::default() });
+            }
+        }
+    }
+
+    /// Set whether flinging again while a fling is still under way, in the same direction,
+    /// adds on to what's left of it rather than replacing it
+    ///
+    /// Lets repeated flicks build up speed like spinning a wheel. The combined velocity is
+    /// capped at `max_fling_velocity` (px/ms). A fling the other way replaces the old one as
+    /// usual. Defaults to off
+    pub fn set_fling_inheritance(&mut self, enabled: bool) {
+        self.x.set_fling_inheritance(enabled);
+        self.y.set_fling_inheritance(enabled);
+    }
+
+    /// Set how the pan log is kept within `event_expiry_count` events during long drags
+    ///
+    /// Only the older half of the log is ever thinned, so the velocity estimate, which comes
+    /// from the newest events, is unaffected by a compaction
+    pub fn set_log_compaction(&mut self, mode: CompactionMode) {
+        self.x.set_compaction(mode);
+        self.y.set_compaction(mode);
+    }
+
+    /// Set how old, in ms, logged pan events may get before step_frame() drops them
+    ///
+    /// Guards against a gesture whose input stopped arriving without an interrupt (the app
+    /// was backgrounded, the device disconnected) leaving stale events behind. If every event
+    /// of a pan in progress ages out, the pan is stopped with no velocity, so a fling pushed
+    /// later can't pick up where the old one left off. Defaults to unbounded
+    pub fn set_max_sample_age_ms(&mut self, ms: f64) {
+        if ms.is_nan() {
+            return;
+        }
+
+        self.x.set_max_sample_age(ms);
+        self.y.set_max_sample_age(ms);
+    }
+
+    /// Set how pan input is resampled to frame times once it's logged
+    pub fn set_resampling(&mut self, mode: ResampleMode) {
+        self.x.set_resampling(mode);
+        self.y.set_resampling(mode);
+    }
+
+    /// Set a linear scale applied to every pan delta as it's pushed, before acceleration
+    ///
+    /// Intended for devices whose deltas are in units that don't map 1:1 to pixels. Fling
+    /// velocity is derived from the scaled deltas, so flings stay consistent with tracking.
+    /// Defaults to 1.0
+    pub fn set_drag_sensitivity(&mut self, sensitivity: f64) {
+        if !finite_input("set_drag_sensitivity()", &[sensitivity]) {
+            return;
+        }
+
+        self.drag_sensitivity = sensitivity;
+    }
+
+    /// Set the precision, in px, used when deciding whether content has stopped
+    ///
+    /// This is used in two places:
+    ///  - a fling counts a frame toward settling when it moved less than this since the last one
+    ///  - content is only considered past a bound once it's further than this beyond it
+    ///
+    /// Defaults to 0.01. Tighten it for high-DPI output, or loosen it to settle sooner. When
+    /// flings stop is set apart from this, with set_velocity_epsilon()
+    pub fn set_epsilon(&mut self, epsilon: f64) {
+        if !finite_input("set_epsilon()", &[epsilon]) {
+            return;
+        }
+
+        self.x.set_epsilon(epsilon);
+        self.y.set_epsilon(epsilon);
+    }
+
+    /// Set the speed, in px/ms, under which content is treated as not moving
+    ///
+    /// A fling slower than this stops decaying and stops, a spring counts toward settling
+    /// (see set_settle_frames()) only while slower than this, and a fling interrupted slower
+    /// than this leaves nothing for set_fling_inheritance() to carry on. Defaults to 0.01
+    pub fn set_velocity_epsilon(&mut self, epsilon: f64) {
+        if !finite_input("set_velocity_epsilon()", &[epsilon]) {
+            return;
+        }
+
+        self.x.set_velocity_epsilon(epsilon);
+        self.y.set_velocity_epsilon(epsilon);
+    }
+
+    /// Set approximately how long it will be from now until content is rendered to screen, so
+    /// that position can be predicted that far ahead
+    ///
+    /// If unsure, 0 is a safe value at the cost of some latency. Ignored while auto prediction
+    /// is on
+    pub fn set_next_frame_predict(&mut self, ms: f64) {
+        if !finite_input("set_next_frame_predict()", &[ms]) {
+            return;
+        }
+
+        self.time_to_pageflip = Some(ms.max(0.0));
+        if !self.auto_prediction {
+            self.x.set_lead(self.time_to_pageflip);
+            self.y.set_lead(self.time_to_pageflip);
+        }
+    }
+
+    /// Set whether the prediction lead should be estimated from the spacing of the timestamps
+    /// passed to mark_frame(), rather than given through set_next_frame_predict()
+    ///
+    /// The estimate is a smoothed average of the intervals between frames, and the lead is
+    /// taken to be one frame. When turned back off, the manual value is used again
+    pub fn set_auto_prediction(&mut self, enabled: bool) {
+        self.auto_prediction = enabled;
+        self.last_frame_time = None;
+
+        let lead = if enabled { Some(self.frametime) } else { self.time_to_pageflip };
+        self.x.set_lead(lead);
+        self.y.set_lead(lead);
+    }
+
+    /// Set the speed, in px/ms, above which position is predicted less far ahead
+    ///
+    /// Past this speed the lead is scaled down in proportion, so the distance predicted ahead
+    /// stays at what it would be at the rolloff speed rather than overshooting during very fast
+    /// flings. Below it, prediction is unaffected. Defaults to no rolloff
+    pub fn set_prediction_velocity_rolloff(&mut self, velocity: f64) {
+        if velocity.is_nan() {
+            return;
+        }
+
+        self.x.set_prediction_rolloff(velocity);
+        self.y.set_prediction_rolloff(velocity);
+    }
+
+    /// Set how many frames in a row a spring (springing back from overscroll or in to a snap
+    /// point) must spend within half a pixel of its bound or target, and moving slower than
+    /// the velocity epsilon (see set_velocity_epsilon()), before it's put there and the
+    /// animation ends
+    ///
+    /// A spring closes in on its last fraction of a pixel slowly, and one tuned with too
+    /// little damping can ring around it indefinitely, neither of which can be seen but both
+    /// of which keep animating() true. Defaults to 10
+    pub fn set_settle_frames(&mut self, frames: usize) {
+        self.x.set_settle_frames(frames);
+        self.y.set_settle_frames(frames);
+    }
+
+    /// Set whether a fling that comes to rest is placed exactly on its resting position
+    ///
+    /// A fling settles once it moves less than epsilon (see set_epsilon()), which can leave it a
+    /// fraction of a pixel to either side of a bound it bounced off, with a trace of velocity.
+    /// With this on, that remainder is dropped when it settles, so get_position_absolute()
+    /// gives exactly the bound (or the snap point, see set_grid_snap()) once animating() is
+    /// false. Defaults to on
+    pub fn set_exact_rest(&mut self, enabled: bool) {
+        self.x.set_exact_rest(enabled);
+        self.y.set_exact_rest(enabled);
+    }
+
+    /// Set whether the position reported between sparse input is smoothed
+    ///
+    /// At low input rates (a 30Hz mouse on a 120Hz display, say) the tracked position can
+    /// change speed with every event. With this on, get_position_absolute() instead moves
+    /// along the velocity seen over recent frames, closing in on the tracked position without
+    /// ever passing it. Unlike prediction this never leads ahead of input, it only fills in the
+    /// gaps between updates, so it can lag slightly. Defaults to off
+    pub fn set_output_interpolation(&mut self, enabled: bool) {
+        self.x.set_output_interpolation(enabled);
+        self.y.set_output_interpolation(enabled);
+    }
+
+    /// Set whether position on the given axis is predicted ahead at all
+    ///
+    /// Useful on a cross axis that only moves occasionally, where leading ahead of small,
+    /// noisy movement shows up as sideways jitter. A disabled axis follows input exactly, with
+    /// no lead from set_next_frame_predict() or auto prediction. Both axes default to enabled
+    pub fn set_prediction_enabled_axis(&mut self, axis: Axis, enabled: bool) {
+        match axis {
+            Axis::Horizontal => self.x.set_prediction_enabled(enabled),
+            Axis::Vertical => self.y.set_prediction_enabled(enabled),
+        }
+    }
+
+    /// Play a short bounce past the nearest bound on the given axis, reaching about `distance`
+    /// px beyond it before springing back, to hint that the content scrolls
+    ///
+    /// Reuses the overscroll spring, so the return feels the same as after a fling. animating()
+    /// is true until the bounce settles. Any pan or fling in progress on the axis is replaced
+    pub fn play_peek<T: Timestamp>(&mut self, axis: Axis, distance: f64, timestamp: T) {
+        if !finite_input("play_peek()", &[distance, timestamp.as_millis()]) {
+            return;
+        }
+
+        match axis {
+            Axis::Horizontal => self.x.peek(timestamp.as_millis(), distance),
+            Axis::Vertical => self.y.peek(timestamp.as_millis(), distance),
+        }
+    }
+
+    /// Halt all animation without losing any state, for instance while the view is off-screen
+    ///
+    /// While frozen, step_frame() keeps producing the position at the time of the freeze and
+    /// animating() is false
+    pub fn freeze(&mut self) {
+        self.x.freeze();
+        self.y.freeze();
+    }
+
+    /// Resume animation after a freeze()
+    ///
+    /// The next step_frame() picks up exactly where the freeze left off, as though no time had
+    /// passed in between
+    pub fn unfreeze(&mut self) {
+        self.x.unfreeze();
+        self.y.unfreeze();
+    }
+
+    /// Set the seed that anything randomized in this scrollview draws from, so that a recorded
+    /// session replayed with the same seed, events and timestamps comes out identically
+    ///
+    /// Each view keeps its own random state, which this restarts from the given seed. Nothing
+    /// draws from it yet: everything a scrollview reports follows only from the events and
+    /// timestamps it's given (and the clock, for events without one), so this currently
+    /// affects no behavior. Anything that comes to use randomness, such as dithering sub-pixel
+    /// positions, will draw from this seed and be listed here. Defaults to 0
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = SeededRng::new(seed);
+    }
+
+    /// Gives the seed set with set_seed()
+    pub fn seed(&self) -> u64 {
+        self.rng.seed
+    }
+
+    /// Set the clock that gives the time for events pushed with a timestamp of None
+    ///
+    /// Defaults to SystemClock, which agrees with `Instant` timestamps. Use a clock sharing the
+    /// epoch of whatever timestamps are passed elsewhere, or a MockClock to control time exactly
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// Set what device type is going to be providing any events that follow until the next source
+    /// is declared
+    pub fn set_source(&mut self, source: Source) {
+        self.current_source = source;
+        self.x.set_source(source);
+        self.y.set_source(source);
+    }
+
+    /// Gives the total distance scrolled along the given axis since creation or the last
+    /// reset_total_scrolled(), counting pans and flings in either direction
+    ///
+    /// Only ever grows, regardless of where the content ends up
+    pub fn total_scrolled(&self, axis: Axis) -> f64 {
+        match axis {
+            Axis::Horizontal => self.x.total_scrolled(),
+            Axis::Vertical => self.y.total_scrolled(),
+        }
+    }
+
+    /// Resets the distance reported by total_scrolled() to zero on both axes
+    pub fn reset_total_scrolled(&mut self) {
+        self.x.reset_total_scrolled();
+        self.y.reset_total_scrolled();
+    }
+
+    /// Set whether an axis that can't scroll (content fits exactly within the viewport) should
+    /// still rubber-band when panned, for a "bouncy empty list" feel
+    ///
+    /// If false, pan attempts on such an axis are ignored entirely. Defaults to true
+    pub fn set_empty_overscroll(&mut self, enabled: bool) {
+        self.x.set_empty_overscroll(enabled);
+        self.y.set_empty_overscroll(enabled);
+    }
+
+    /// Cap how far past either bound content can be pulled on the given axis, in px
+    ///
+    /// Once rubber-banding reaches the cap, further pan outward is absorbed entirely (and
+    /// reported as such by push_pan()), so content can't be dragged out of view. Flings that
+    /// hit the cap stop there and spring back. Defaults to unbounded
+    pub fn set_max_overscroll(&mut self, axis: Axis, distance: f64) {
+        if distance.is_nan() {
+            return;
+        }
+
+        match axis {
+            Axis::Horizontal => self.x.set_max_overscroll(distance),
+            Axis::Vertical => self.y.set_max_overscroll(distance),
+        }
+    }
+
+    /// Set how strongly pans resist being pulled past a bound, with higher values following
+    /// the finger less. Applies while dragging only, see set_fling_overscroll_limit() for
+    /// momentum
+    ///
+    /// Must be positive. Defaults to the config's overscroll_elasticity_coefficient
+    pub fn set_drag_overscroll_resistance(&mut self, resistance: f64) {
+        if !finite_input("set_drag_overscroll_resistance()", &[resistance]) || resistance <= 0.0 {
+            return;
+        }
+
+        self.x.set_drag_overscroll_resistance(resistance);
+        self.y.set_drag_overscroll_resistance(resistance);
+    }
+
+    /// Cap how far momentum alone can carry content past a bound before it springs back, in px
+    ///
+    /// Lets a fling bounce less than a drag is allowed to stretch, as on iOS. A fling released
+    /// while already further out than this isn't pulled in to it, only stopped from going
+    /// further. set_max_overscroll() still applies on top. Defaults to unbounded
+    pub fn set_fling_overscroll_limit(&mut self, distance: f64) {
+        if distance.is_nan() {
+            return;
+        }
+
+        self.x.set_fling_overscroll_limit(distance);
+        self.y.set_fling_overscroll_limit(distance);
+    }
+
+    /// Set the range over which pan acceleration applies on the given axis
+    ///
+    /// Velocities below `min_velocity` are tracked 1:1 with no acceleration, and the curve is
+    /// applied only to the excess above it. Accelerated velocity is capped at `max_multiplier`
+    /// times the input, so very fast pans don't run away. Defaults to 0 and unbounded
+    pub fn set_accel_range(&mut self, axis: Axis, min_velocity: f64, max_multiplier: f64) {
+        // an infinite multiplier is how "no cap" is spelled, so only NaN is rejected there
+        if !finite_input("set_accel_range()", &[min_velocity]) || max_multiplier.is_nan() {
+            return;
+        }
+
+        match axis {
+            Axis::Horizontal => self.x.set_accel_range(min_velocity, max_multiplier),
+            Axis::Vertical => self.y.set_accel_range(min_velocity, max_multiplier),
+        }
+    }
+
+    /// Set whether pan acceleration applies while dragging, or only to flings
+    ///
+    /// When off, pans are tracked without acceleration while the pointer is down (as they
+    /// would be from a touchscreen), and the acceleration curve is applied once to the velocity
+    /// a fling starts with, so flings keep their momentum as on iOS. Only matters for sources
+    /// that accelerate. Defaults to true
+    pub fn set_accel_affects_drag(&mut self, enabled: bool) {
+        self.x.set_accel_affects_drag(enabled);
+        self.y.set_accel_affects_drag(enabled);
+    }
+
+    /// Scale the momentum flings start with on both axes, so 0.5 travels about half as far and
+    /// 2.0 about twice as far. Defaults to 1.0
+    pub fn set_fling_multiplier(&mut self, multiplier: f64) {
+        self.set_fling_multiplier_axis(Axis::Horizontal, multiplier);
+        self.set_fling_multiplier_axis(Axis::Vertical, multiplier);
+    }
+
+    /// Scale the momentum flings start with on one axis only, for asymmetric feel such as a
+    /// feed that glides far vertically inside a pager that stops quickly horizontally
+    ///
+    /// Applied once as each fling starts, so it also scales velocity inherited by a following
+    /// fling. Negative values are ignored. Defaults to 1.0
+    pub fn set_fling_multiplier_axis(&mut self, axis: Axis, multiplier: f64) {
+        if !finite_input("set_fling_multiplier_axis()", &[multiplier]) || multiplier < 0.0 {
+            return;
+        }
+
+        match axis {
+            Axis::Horizontal => self.x.set_fling_multiplier(multiplier),
+            Axis::Vertical => self.y.set_fling_multiplier(multiplier),
+        }
+    }
+
+    /// Set whether flings are scaled by the scroll extent, so the same flick carries further
+    /// through long content and less far through short content
+    ///
+    /// Fling velocity is scaled in proportion to the extent, relative to a few screens' worth
+    /// of content, and within a factor of 4 either way. Applied as each fling starts, on top of
+    /// set_fling_multiplier(). Defaults to off
+    pub fn set_fling_scale_by_extent(&mut self, enabled: bool) {
+        self.x.set_fling_scale_by_extent(enabled);
+        self.y.set_fling_scale_by_extent(enabled);
+    }
+
+    /// Gives what the given input velocity becomes after the pan acceleration configured for
+    /// an axis (including set_accel_range()), for plotting the curve in a settings ui
+    ///
+    /// Applies the curve whether or not the current source is one that accelerates
+    pub fn preview_accel(&self, axis: Axis, input: f64) -> f64 {
+        match axis {
+            Axis::Horizontal => self.x.preview_accel(input),
+            Axis::Vertical => self.y.preview_accel(input),
+        }
+    }
+
+    /// Gives how much further, in px and signed by direction, the fling in progress on the given
+    /// axis will carry the content before it comes to rest
+    ///
+    /// Accounts for the fling model, any bounce back from a bound, and grid snapping, by
+    /// running the fling on to completion on a copy. 0 if the axis isn't flinging
+    pub fn remaining_fling_distance(&self, axis: Axis) -> f64 {
+        let track = match axis {
+            Axis::Horizontal => &self.x,
+            Axis::Vertical => &self.y,
+        };
+
+        track.rest_position() - track.position()
+    }
+
+    /// Gives how far, in px, the content on the given axis would end up past its bounds if its
+    /// momentum ran out with no spring pulling it back, for deciding whether a flick dismisses
+    /// a sheet or lets it snap back
+    ///
+    /// Combines the current overscroll with however far the fling would carry on under the fling
+    /// model, so a short drag thrown hard and a long drag let go slowly can be judged against one
+    /// threshold. Positive past the end of the content and negative past its start, 0 if it
+    /// would stop within the bounds.
+    ///
+    /// A drag in progress is projected as if released now. Ask before push_release(), since a
+    /// fling let go while overscrolled is kept from going much further out as soon as it starts
+    pub fn projected_overscroll(&self, axis: Axis) -> f64 {
+        match axis {
+            Axis::Horizontal => self.x.projected_overscroll(),
+            Axis::Vertical => self.y.projected_overscroll(),
+        }
+    }
+
+    /// Gives the velocity (px/ms) that a fling released at `velocity` would have
+    /// decayed to after `elapsed_ms`, under the fling model configured for an axis
+    pub fn preview_decay(&self, axis: Axis, velocity: f64, elapsed_ms: f64) -> f64 {
+        match axis {
+            Axis::Horizontal => self.x.preview_decay(velocity, elapsed_ms),
+            Axis::Vertical => self.y.preview_decay(velocity, elapsed_ms),
+        }
+    }
+
+    /// Apply every tuning value in the given profile
+    ///
+    /// The profile applies to this scrollview alone, on both axes. Values it gives from the
+    /// config (acceleration curve, spring and thresholds) are used in place of the config
+    /// file's from then on, so rereading the file doesn't undo them
+    pub fn apply_profile(&mut self, profile: &ScrollProfile) {
+        self.set_drag_sensitivity(profile.drag_sensitivity);
+        self.set_accel_range(Axis::Horizontal, profile.accel_min_velocity, profile.accel_max_multiplier);
+        self.set_accel_range(Axis::Vertical, profile.accel_min_velocity, profile.accel_max_multiplier);
+        self.set_fling_model(profile.fling_model);
+        self.set_fling_inheritance(profile.fling_inheritance);
+        self.set_epsilon(profile.epsilon);
+        self.set_velocity_epsilon(profile.velocity_epsilon);
+        self.set_prediction_velocity_rolloff(profile.prediction_rolloff);
+
+        self.x.set_profile(*profile);
+        self.y.set_profile(*profile);
+    }
+
+    /// Gives the tuning currently in effect as a profile, taking per-axis values from the
+    /// vertical axis
+    pub fn current_profile(&self) -> ScrollProfile {
+        let config = self.y.config();
+        let (accel_min_velocity, accel_max_multiplier) = self.y.accel_range();
+
+        ScrollProfile {
+            drag_sensitivity: self.drag_sensitivity,
+
+            accel_min_velocity,
+            accel_max_multiplier,
+            acceleration_exponent: config.ACCELERATION_EXPONENT,
+            accel_decel_descriminant: config.ACCEL_DECEL_DESCRIMINANT,
+            pre_acceleration_scale_velocity: config.PRE_ACCEL_SCALE_VELOCITY,
+            post_acceleration_scale_velocity: config.POST_ACCEL_SCALE_VELOCITY,
+
+            fling_model: self.y.fling_model(),
+            fling_boost_constant_factor: config.FLING_BOOST_CONSTANT_FACTOR,
+            max_fling_velocity: config.MAX_FLING_VELOCITY,
+            fling_inheritance: self.y.fling_inheritance(),
+
+            content_mass_value: config.CONTENT_MASS_VALUE,
+            overscroll_spring_constant: config.OVERSCROLL_SPRING_CONSTANT,
+            bounce_damping_factor: config.BOUNCE_DAMP_FACTOR,
+            overscroll_elasticity_coefficient: config.OVERSCROLL_ELASTICITY_COEFFICIENT,
+
+            min_velocity_to_idle: config.MIN_VELOCITY_TO_IDLE,
+            min_fling_velocity: config.MIN_FLING_VELOCITY,
+            snap_velocity_threshold: config.SNAP_VELOCITY_THRESHOLD,
+            epsilon: self.y.epsilon(),
+            velocity_epsilon: self.y.velocity_epsilon(),
+            prediction_rolloff: self.y.prediction_rolloff(),
+        }
+    }
+
+    /// Register a callback to be run once scrolling comes fully to rest, given the final
+    /// position
+    ///
+    /// Fires from within step_frame() on the frame animating() turns false, and not again until
+    /// a new gesture has started and settled in turn. Freezing doesn't count as settling.
+    /// Like set_on_reach_edge(), the callback must not reach back into the scrollview
+    pub fn set_on_settle(&mut self, callback: Box<dyn FnMut(AxisVector<f64>) + Send>) {
+        self.on_settle = Some(callback);
+    }
+
+    /// Register a callback to be run when scrolling starts after having been at rest, such as
+    /// for showing a scrollbar
+    ///
+    /// Fires from within step_frame() on the frame animating() turns true. Each begin is
+    /// followed by exactly one end (see set_on_scroll_end()) before the next begin
+    pub fn set_on_scroll_begin(&mut self, callback: Box<dyn FnMut() + Send>) {
+        self.on_scroll_begin = Some(callback);
+    }
+
+    /// Register a callback to be run when scrolling comes to rest, such as for fading out a
+    /// scrollbar
+    ///
+    /// Fires on the same frame as set_on_settle(), after it, for callers that don't need the
+    /// position
+    pub fn set_on_scroll_end(&mut self, callback: Box<dyn FnMut() + Send>) {
+        self.on_scroll_end = Some(callback);
+    }
+
+    /// Register a callback to be run when a pan or fling comes within `edge_trigger_distance`
+    /// of the given edge, intended for loading more content in infinite scrollviews
+    ///
+    /// If the callback returns a new content extent (height for Top/Bottom, width for
+    /// Left/Right), the bound is moved out to match before the next step, so a fling continues
+    /// on through without bouncing. Growth past a Top or Left edge is treated as prepended
+    /// content, and the position is offset by the same amount to keep the visible content still.
+    /// Returning None leaves the geometry alone. The callback fires once per approach, and
+    /// again only after the position has moved back out past the trigger distance. A view
+    /// already within the trigger distance when the callback is registered counts as
+    /// approaching, so it fires on the next step_frame().
+    ///
+    /// The callback is run from within step_frame(), while the scrollview is mutably borrowed, so it
+    /// must not try to reach back into the scrollview (for instance through a shared RefCell).
+    /// Report the new extent through the return value instead
+    pub fn set_on_reach_edge(&mut self, edge: Edge, callback: Box<dyn FnMut() -> Option<f64> + Send>) {
+        // armed from the start, so content that already begins near the edge (a short first
+        // page, say) still gets to load more without being scrolled away and back
+        self.edge_hooks.insert(edge, EdgeHook { callback, armed: true });
+    }
+
+    /// Register a point `distance` px past the given edge to be reported through
+    /// triggered_this_frame() whenever overscroll crosses it, as for arming pull to refresh or
+    /// firing a haptic
+    ///
+    /// Crossings are reported going out (arming the trigger) and coming back in (disarming
+    /// it), see is_overscroll_trigger_armed() for which. Registering an id again replaces it
+    pub fn add_overscroll_trigger(&mut self, edge: Edge, distance: f64, id: u64) {
+        if !finite_input("add_overscroll_trigger()", &[distance]) {
+            return;
+        }
+
+        self.overscroll_triggers.retain(|trigger| trigger.id != id);
+        self.overscroll_triggers.push(OverscrollTrigger { edge, distance: distance.abs(), id, armed: false });
+    }
+
+    pub fn remove_overscroll_trigger(&mut self, id: u64) {
+        self.overscroll_triggers.retain(|trigger| trigger.id != id);
+    }
+
+    /// Gives the ids of the overscroll triggers crossed, in either direction, during the most
+    /// recent step_frame()
+    pub fn triggered_this_frame(&self) -> Vec<u64> {
+        self.triggered.clone()
+    }
+
+    /// True if overscroll was past the given trigger's distance as of the most recent
+    /// step_frame()
+    pub fn is_overscroll_trigger_armed(&self, id: u64) -> bool {
+        self.overscroll_triggers.iter().any(|trigger| trigger.id == id && trigger.armed)
+    }
+
+    /// Runs a pan delta through each input filter in turn
+    fn apply_input_filters(&mut self, axis: Axis, amount: f64, time: f64) -> f64 {
+        let last = match axis {
+            Axis::Horizontal => self.last_pan_time.0.replace(time),
+            Axis::Vertical => self.last_pan_time.1.replace(time),
+        };
+        let dt = last.map_or(0.0, |last| time - last);
+
+        self.input_filters.iter_mut().fold(amount, |delta, filter| filter.filter(axis, delta, dt))
+    }
+
+    /// Hands the velocity tracker's estimate, if there is one, to each axis for its release
+    fn apply_velocity_tracker(&mut self) {
+        let tracker = match self.velocity_tracker.as_ref() {
+            Some(tracker) => tracker,
+            None => return,
+        };
+
+        let (x, y) = (tracker.estimate(Axis::Horizontal), tracker.estimate(Axis::Vertical));
+        if finite_input("VelocityTracker::estimate()", &[x, y]) {
+            self.x.set_release_velocity(x);
+            self.y.set_release_velocity(y);
+        }
+    }
+
+    /// Whether a pan is far larger than the ones just before it on its axis, as from a palm
+    /// brushing the screen, remembering its size for judging the next one if not
+    ///
+    /// Only one pan in a row is dropped, so a pan that really did speed up sharply is let
+    /// through on its second large delta and becomes the new trend
+    fn is_outlier(&mut self, axis: Axis, amount: f64) -> bool {
+        let factor = match self.outlier_factor {
+            Some(factor) => factor,
+            None => return false,
+        };
+
+        let (recent, dropped) = match axis {
+            Axis::Horizontal => (&mut self.recent_deltas.0, &mut self.outlier_dropped.0),
+            Axis::Vertical => (&mut self.recent_deltas.1, &mut self.outlier_dropped.1),
+        };
+
+        let size = amount.abs();
+        let trend = recent.iter().sum::<f64>() / recent.len().max(1) as f64;
+        // an axis that's been still has no trend to stand out from
+        if recent.len() >= OUTLIER_MIN_HISTORY && !*dropped && trend > 0.0 && size > trend * factor {
+            *dropped = true;
+            return true;
+        }
+
+        *dropped = false;
+        recent.push_back(size);
+        if recent.len() > OUTLIER_HISTORY {
+            recent.pop_front();
+        }
+
+        false
+    }
+
+    /// Starts outlier rejection over, for a new gesture
+    fn forget_recent_deltas(&mut self) {
+        self.recent_deltas = (VecDeque::new(), VecDeque::new());
+        self.outlier_dropped = (false, false);
+    }
+
+    /// Follows pointer movement for is_holding(), restarting the hold once it's moved too far
+    fn track_hold(&mut self, axis: Axis, amount: f64, time: f64) {
+        self.hold_since.get_or_insert(time);
+
+        match axis {
+            Axis::Horizontal => self.hold_drift.x += amount,
+            Axis::Vertical => self.hold_drift.y += amount,
+        }
+
+        if self.hold_drift.x.hypot(self.hold_drift.y) > HOLD_SLOP_PX {
+            self.hold_since = Some(time);
+            self.hold_drift = AxisVector::default();
+        }
+    }
+
+    fn update_prediction(&mut self, time: f64) {
+        if !self.auto_prediction {
+            return;
+        }
+
+        if let Some(last) = self.last_frame_time {
+            let interval = time - last;
+            if interval > 0.0 && interval < AUTO_PREDICTION_MAX_FRAME_MS {
+                self.frametime = if self.frametime == 0.0 {
+                    interval
+                } else {
+                    self.frametime + (interval - self.frametime) * AUTO_PREDICTION_SMOOTHING
+                };
+
+                self.x.set_lead(Some(self.frametime));
+                self.y.set_lead(Some(self.frametime));
+            }
+        }
+
+        self.last_frame_time = Some(time);
+    }
+
+    fn check_settled(&mut self) {
+        // a freeze stops animation without anything having settled
+        if self.x.frozen() || self.y.frozen() {
+            return;
+        }
+
+        let animating = self.animating();
+        if !self.was_animating && animating {
+            if let Some(callback) = self.on_scroll_begin.as_mut() {
+                callback();
+            }
+        }
+
+        if self.was_animating && !animating {
+            let position = AxisVector {
+                x: self.x.position(),
+                y: self.y.position(),
+                ..Default::default()
+            };
+
+            if let Some(callback) = self.on_settle.as_mut() {
+                callback(position);
+            }
+
+            if let Some(callback) = self.on_scroll_end.as_mut() {
+                callback();
+            }
+        }
+
+        self.was_animating = animating;
+    }
+
+    fn update_bounds(&mut self) {
+        // content smaller than the viewport would put the upper bound below the lower one, so
+        // it's held at the lower, leaving content resting at its start
+        let (left, top) = (-self.inset_left, -self.inset_top);
+        self.x.set_geometry(left, (self.content_width - self.viewport_width + self.inset_right).max(left));
+        self.y.set_geometry(top, (self.content_height - self.viewport_height + self.inset_bottom).max(top));
+    }
+
+    fn check_overscroll_triggers(&mut self) {
+        self.triggered.clear();
+
+        for trigger in self.overscroll_triggers.iter_mut() {
+            let track = match trigger.edge.axis() {
+                Axis::Horizontal => &self.x,
+                Axis::Vertical => &self.y,
+            };
+            let position = track.position();
+            let (lower, upper) = track.bounds();
+
+            let overscroll = match trigger.edge {
+                Edge::Top | Edge::Left => lower - position,
+                Edge::Bottom | Edge::Right => position - upper,
+            };
+
+            let armed = overscroll >= trigger.distance;
+            if armed != trigger.armed {
+                trigger.armed = armed;
+                self.triggered.push(trigger.id);
+            }
+        }
+    }
+
+    fn check_edges(&mut self) {
+        let distance = CONFIG.read().unwrap().EDGE_TRIGGER_DISTANCE;
+
+        let mut extensions = Vec::new();
+        for (&edge, hook) in self.edge_hooks.iter_mut() {
+            let track = match edge.axis() {
+                Axis::Horizontal => &self.x,
+                Axis::Vertical => &self.y,
+            };
+            let position = track.position();
+            let (lower, upper) = track.bounds();
+
+            let near = match edge {
+                Edge::Top | Edge::Left => position <= lower + distance,
+                Edge::Bottom | Edge::Right => position >= upper - distance,
+            };
+
+            if !near {
+                hook.armed = true;
+            } else if hook.armed {
+                hook.armed = false;
+                // anything that couldn't be a content extent is ignored as if None came back,
+                // before it has a chance to move the position
+                if let Some(extent) = (hook.callback)() {
+                    if finite_input("set_on_reach_edge() callback", &[extent]) && extent >= 0.0 {
+                        extensions.push((edge, extent));
+                    }
+                }
+            }
+        }
+
+        if extensions.is_empty() {
+            return;
+        }
+
+        for (edge, extent) in extensions {
+            match edge {
+                Edge::Top => {
+                    self.y.offset(extent - self.content_height);
+                    self.content_height = extent;
+                },
+                Edge::Left => {
+                    self.x.offset(extent - self.content_width);
+                    self.content_width = extent;
+                },
+                Edge::Bottom => self.content_height = extent,
+                Edge::Right => self.content_width = extent,
+            }
+
+            // the edge is now further out, so let it be reached again
+            if let Some(hook) = self.edge_hooks.get_mut(&edge) {
+                hook.armed = true;
+            }
+        }
+
+        self.set_geometry(self.content_height, self.content_width, self.viewport_height, self.viewport_width);
+    }
+
+    /// Whether an event at the given time falls within the lockout window after a zoom
+    fn zoom_locked_out(&self, time: f64) -> bool {
+        self.zoom_ended_at.is_some_and(|ended| time - ended < self.zoom_lockout_ms)
+    }
+
+    /// The time of an event, taken from the clock if the caller didn't give one
+    fn event_time(&self, timestamp: Option<u64>) -> f64 {
+        timestamp.map_or_else(|| self.clock.now(), |t| t.as_millis())
+    }
+
+    fn update_config() {
+        let mut config_struct = CONFIG.write().expect("Couldn't lock config struct");
+
+        let mut config_dir: std::path::PathBuf = app_root(AppDataType::UserConfig, &APP_INFO).unwrap(); // TODO: gracefully handle
+        let config_file = std::path::PathBuf::from("config.ini");
+        config_dir.push(config_file);
+
+        let _ = Ini::from_file(&config_dir).map(|config| {
+            println!("Found config file, applying...");
+            config.get("config", "event_expiry_count").map(|v: usize| { config_struct.EVENT_EXPIRY_COUNT = v});
+            config.get("config", "sample_expiry_count").map(|v: usize| { config_struct.SAMPLE_EXPIRY_COUNT = v});
+            config.get("config", "confidence_full_event_count").map(|v: usize| { config_struct.CONFIDENCE_FULL_EVENT_COUNT = v});
+
+            config.get("config", "ticks_to_coast").map(|v: f64| { config_struct.TICKS_TO_COAST = v});
+            config.get("config", "timestep").map(|v: f64| { config_struct.TIMESTEP = v});
+            config.get("config", "min_velocity_to_idle").map(|v: f64| { config_struct.MIN_VELOCITY_TO_IDLE = v});
+            config.get("config", "post_acceleration_scale_velocity").map(|v: f64| { config_struct.POST_ACCEL_SCALE_VELOCITY = v});
+            config.get("config", "pre_acceleration_scale_velocity").map(|v: f64| { config_struct.PRE_ACCEL_SCALE_VELOCITY = v});
+            config.get("config", "shift_window_ms").map(|v: f64| { config_struct.SHIFT_WINDOW_MS = v});
+            config.get("config", "overscroll_elasticity_coefficient").map(|v: f64| { config_struct.OVERSCROLL_ELASTICITY_COEFFICIENT = v});
+            config.get("config", "content_mass_value").map(|v: f64| { config_struct.CONTENT_MASS_VALUE = v});
+            config.get("config", "overscroll_spring_constant").map(|v: f64| { config_struct.OVERSCROLL_SPRING_CONSTANT = v});
+            config.get("config", "bounce_damping_factor").map(|v: f64| { config_struct.BOUNCE_DAMP_FACTOR = v});
+            config.get("config", "zero_delta_injection_wait_ms").map(|v: f64| { config_struct.MAX_MS_WITHOUT_ZERO_INJECTION = v});
+            config.get("config", "first_event_multiplier").map(|v: f64| { config_struct.MULTIPLY_FIRST_EVENT = v});
+            config.get("config", "accel_decel_descriminant").map(|v: f64| { config_struct.ACCEL_DECEL_DESCRIMINANT = v});
+            config.get("config", "acceleration_exponent").map(|v: f64| { config_struct.ACCELERATION_EXPONENT = v});
+            config.get("config", "fling_boost_constant_factor").map(|v: f64| { config_struct.FLING_BOOST_CONSTANT_FACTOR = v});
+            config.get("config", "edge_trigger_distance").map(|v: f64| { config_struct.EDGE_TRIGGER_DISTANCE = v});
+            config.get("config", "snap_velocity_threshold").map(|v: f64| { config_struct.SNAP_VELOCITY_THRESHOLD = v});
+            config.get("config", "max_fling_velocity").map(|v: f64| { config_struct.MAX_FLING_VELOCITY = v});
+            config.get("config", "min_fling_velocity").map(|v: f64| { config_struct.MIN_FLING_VELOCITY = v});
+
+            config.get("config", "flips_until_idle").map(|v: u64| { config_struct.FLIPS_TO_IDLE = v});
+        }).map_err(|_| {
+            println!("Couldn't find config file");
+            // maybe add message if can't be found?
+        });
+    }
+}
+
+/// Blend between the displayed positions of two scrollviews, for transitions where one scroll
+/// state morphs into another
+///
+/// Gives a's position at t = 0 and b's at t = 1. t isn't clamped, so an eased t that
+/// overshoots carries the blend past either end. Neither scrollview is touched
+pub fn lerp(a: &Scrollview, b: &Scrollview, t: f64) -> AxisVector<f64> {
+    let (from, to) = (a.get_position_absolute(), b.get_position_absolute());
+
+    from + (to - from).scale(t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    // a vertically scrolling view with 9000px to scroll through, on a clock that stays put
+    fn view() -> Scrollview {
+        let mut view = Scrollview::new();
+        view.set_clock(Box::new(MockClock::new(1.0)));
+        view.set_geometry(10000.0, 1000.0, 1000.0, 1000.0);
+        view
+    }
+
+    // drags down by delta every interval ms, stepping a frame after each, and gives the time of
+    // the last pan
+    fn drag(view: &mut Scrollview, start: f64, delta: f64, interval: f64, count: usize) -> f64 {
+        view.push_interrupt_at(start);
+        let mut time = start;
+        for _ in 0..count {
+            time += interval;
+            view.push_pan_at(Axis::Vertical, delta, time);
+            view.step_frame(time);
+        }
+        time
+    }
+
+    // steps frames every 8ms until the view comes to rest, giving the time it got there
+    fn settle(view: &mut Scrollview, mut time: f64) -> f64 {
+        for _ in 0..2000 {
+            if !view.animating() {
+                break;
+            }
+            time += 8.0;
+            view.step_frame(time);
+        }
+        assert!(!view.animating(), "never came to rest");
+        time
+    }
+
+    #[test]
+    fn untimed_events_take_no_annotations() {
+        let mut view = view();
+        view.push_interrupt(None);
+        view.push_pan(Axis::Vertical, 10.0, None);
+        view.push_release(None);
+        view.push_pan(Axis::Vertical, 10.0, Some(40));
+        view.push_fling(Some(48));
+    }
+
+    #[test]
+    fn submillisecond_pans_fling_and_stop() {
+        let mut view = view();
+        let time = drag(&mut view, 100.0, 5.0, 0.6, 10);
+        view.push_fling_at(time);
+
+        settle(&mut view, time);
+        assert!(view.get_pos_y() > 50.0);
+    }
+
+    #[test]
+    fn float_timestamps_are_milliseconds() {
+        let (mut a, mut b) = (view(), view());
+        let end = drag(&mut a, 100.0, 10.0, 8.0, 5);
+        for i in 0..=5u64 {
+            if i == 0 {
+                b.push_interrupt(Some(100));
+            } else {
+                b.push_pan(Axis::Vertical, 10.0, Some(100 + 8 * i));
+                b.step_frame(100 + 8 * i);
+            }
+        }
+
+        assert_eq!(end, 140.0);
+        assert_eq!(a.get_pos_y(), b.get_pos_y());
+    }
+
+    // under strict, non-finite input is misuse and panics instead
+    #[cfg(not(feature = "strict"))]
+    #[test]
+    fn non_finite_input_leaves_view_usable() {
+        let mut view = view();
+        let time = drag(&mut view, 100.0, 10.0, 8.0, 5);
+
+        assert_eq!(view.push_pan_at(Axis::Vertical, f64::NAN, time + 8.0), PanOutcome::Rejected);
+        view.push_pan_at(Axis::Vertical, 10.0, f64::INFINITY);
+        view.set_geometry(f64::NAN, 1000.0, f64::INFINITY, 1000.0);
+        view.set_next_frame_predict(f64::NAN);
+        view.step_frame(time + 8.0);
+        assert!(view.get_pos_y().is_finite());
+
+        let before = view.get_pos_y();
+        let time = drag(&mut view, time + 16.0, 10.0, 8.0, 5);
+        assert!(view.get_pos_y() > before);
+        view.push_fling_at(time);
+        settle(&mut view, time);
+        assert!(view.get_pos_y().is_finite());
+        assert_eq!(view.scroll_extent(Axis::Vertical), 9000.0);
+    }
+
+    #[test]
+    fn mock_clock_drives_a_view_on_another_thread() {
+        let clock = MockClock::new(100.0);
+        let mut view = view();
+        view.set_clock(Box::new(clock.clone()));
+
+        let worker = std::thread::spawn(move || {
+            view.push_interrupt(None);
+            view
+        });
+        let mut view = worker.join().unwrap();
+
+        for _ in 0..5 {
+            clock.advance(8.0);
+            view.push_pan(Axis::Vertical, 10.0, None);
+            view.step_frame(clock.now());
+        }
+
+        assert_eq!(clock.now(), 140.0);
+        assert!(view.get_pos_y() > 0.0);
+    }
+
+    // flings diagonally from the middle of content scrollable both ways, giving how far each
+    // axis traveled after release
+    fn diagonal_fling(view: &mut Scrollview) -> (f64, f64) {
+        view.set_geometry(100000.0, 100000.0, 1000.0, 1000.0);
+        view.adjust_position(AxisVector { x: 50000.0, y: 50000.0, ..Default::default() });
+        view.push_interrupt_at(100.0);
+        let mut time = 100.0;
+        for _ in 0..10 {
+            time += 8.0;
+            view.push_wheel_2d_at(20.0, 20.0, WheelModifier::None, time);
+            view.step_frame(time);
+        }
+        let start = view.get_position_absolute();
+        view.push_fling_at(time);
+        settle(view, time);
+        let end = view.get_position_absolute();
+
+        (end.x - start.x, end.y - start.y)
+    }
+
+    #[test]
+    fn fling_multipliers_apply_per_axis() {
+        let mut view = view();
+        view.set_fling_multiplier_axis(Axis::Horizontal, 0.5);
+        view.set_fling_multiplier_axis(Axis::Vertical, 2.0);
+        let (x, y) = diagonal_fling(&mut view);
+
+        assert!(x > 0.0);
+        assert!(y > 2.0 * x, "x traveled {}, y {}", x, y);
+    }
+
+    #[test]
+    fn negative_fling_multiplier_is_rejected() {
+        let mut plain = view();
+        let expected = diagonal_fling(&mut plain);
+
+        let mut view = view();
+        view.set_fling_multiplier_axis(Axis::Vertical, -2.0);
+
+        assert_eq!(diagonal_fling(&mut view), expected);
+    }
+
+    // counts how many times a reach edge hook fires, giving back extent each time
+    fn edge_counter(view: &mut Scrollview, edge: Edge, extent: Option<f64>) -> Arc<AtomicUsize> {
+        let count = Arc::new(AtomicUsize::new(0));
+        let counted = count.clone();
+        view.set_on_reach_edge(edge, Box::new(move || {
+            counted.fetch_add(1, Ordering::SeqCst);
+            extent
+        }));
+        count
+    }
+
+    #[test]
+    fn reach_edge_fires_when_registered_near_the_edge() {
+        let mut view = view();
+        view.set_geometry(1100.0, 1000.0, 1000.0, 1000.0);
+        let top = edge_counter(&mut view, Edge::Top, None);
+        let bottom = edge_counter(&mut view, Edge::Bottom, Some(5000.0));
+
+        view.step_frame(100);
+        view.step_frame(108);
+
+        assert_eq!(top.load(Ordering::SeqCst), 1);
+        assert_eq!(bottom.load(Ordering::SeqCst), 1);
+        assert_eq!(view.scroll_extent(Axis::Vertical), 4000.0);
+    }
+
+    #[test]
+    fn reach_edge_fires_once_per_approach() {
+        let mut view = view();
+        let bottom = edge_counter(&mut view, Edge::Bottom, None);
+
+        view.step_frame(100);
+        assert_eq!(bottom.load(Ordering::SeqCst), 0);
+
+        view.adjust_position(AxisVector { y: 8990.0, ..Default::default() });
+        view.step_frame(108);
+        view.step_frame(116);
+        assert_eq!(bottom.load(Ordering::SeqCst), 1);
+
+        view.adjust_position(AxisVector { y: -8990.0, ..Default::default() });
+        view.step_frame(124);
+        view.adjust_position(AxisVector { y: 8990.0, ..Default::default() });
+        view.step_frame(132);
+        assert_eq!(bottom.load(Ordering::SeqCst), 2);
+    }
+
+    // under strict, non-finite input is misuse and panics instead
+    #[cfg(not(feature = "strict"))]
+    #[test]
+    fn reach_edge_ignores_a_non_finite_extent() {
+        let mut view = view();
+        let top = edge_counter(&mut view, Edge::Top, Some(f64::NAN));
+
+        view.step_frame(100);
+        view.step_frame(108);
+        assert_eq!(top.load(Ordering::SeqCst), 1);
+        assert_eq!(view.get_pos_y(), 0.0);
+        assert_eq!(view.scroll_extent(Axis::Vertical), 9000.0);
+
+        // nothing was poisoned, so a drag still goes as far as it would have
+        let time = drag(&mut view, 116.0, 10.0, 8.0, 5);
+        assert!(view.get_pos_y() > 0.0 && view.get_pos_y().is_finite());
+        settle(&mut view, time);
+    }
+
+    #[test]
+    fn dominant_axis_holds_through_a_paused_drag() {
+        let mut view = view();
+        view.set_geometry(10000.0, 10000.0, 1000.0, 1000.0);
+        view.push_interrupt_at(100.0);
+        assert_eq!(view.dominant_axis(), None);
+
+        let mut time = 100.0;
+        for _ in 0..5 {
+            time += 8.0;
+            view.push_pan_at(Axis::Horizontal, 2.0, time);
+            view.push_pan_at(Axis::Vertical, 10.0, time);
+            view.step_frame(time);
+        }
+        assert_eq!(view.dominant_axis(), Some(Axis::Vertical));
+
+        // held still long enough for anything in motion to stop
+        let time = settle(&mut view, time) + 500.0;
+        view.step_frame(time);
+        assert!(!view.animating());
+        assert_eq!(view.dominant_axis(), Some(Axis::Vertical));
+
+        view.push_release_at(time);
+        settle(&mut view, time);
+        assert_eq!(view.dominant_axis(), None);
+
+        view.push_interrupt_at(time + 8.0);
+        assert_eq!(view.dominant_axis(), None);
+    }
+
+    #[test]
+    fn profiles_apply_to_one_view_only() {
+        let mut tuned = view();
+        let untuned = view();
+        let stock = untuned.current_profile().overscroll_spring_constant;
+
+        let profile = ScrollProfile { overscroll_spring_constant: stock * 2.0, ..ScrollProfile::default() };
+        tuned.apply_profile(&profile);
+        assert_eq!(tuned.current_profile().overscroll_spring_constant, stock * 2.0);
+        assert_eq!(untuned.current_profile().overscroll_spring_constant, stock);
+
+        // releasing rereads the config file, which mustn't undo the profile
+        let time = drag(&mut tuned, 100.0, 10.0, 8.0, 5);
+        tuned.push_fling_at(time);
+        assert_eq!(tuned.current_profile().overscroll_spring_constant, stock * 2.0);
+    }
+
+    // every derived value a renderer reads, which must all come out as numbers at any geometry
+    fn assert_accessors_finite(view: &Scrollview) {
+        let position = view.get_position_absolute();
+        let transform = view.render_transform();
+        let rect = view.visible_rect();
+        let mut values = vec![
+            position.x, position.y,
+            transform.translate_x, transform.translate_y, transform.scale,
+            rect.x, rect.y, rect.width, rect.height,
+            view.prediction_confidence(),
+        ];
+        for axis in [Axis::Horizontal, Axis::Vertical] {
+            let report = view.position_report(axis);
+            assert!((0.0..=1.0).contains(&report.fraction), "fraction {} out of range", report.fraction);
+            values.extend([report.predicted, report.tracked, report.overscroll]);
+            values.extend([
+                view.scroll_extent(axis),
+                view.total_scrolled(axis),
+                view.remaining_fling_distance(axis),
+                view.projected_overscroll(axis),
+            ]);
+
+            // overscroll may be unbounded, so only the range's bounds may be infinite
+            let (lower, upper) = view.position_range(axis);
+            assert!(!lower.is_nan() && !upper.is_nan());
+        }
+        values.extend(view.fling_progress());
+        for step in view.sample_trajectory(4, 16.0) {
+            values.extend([step.x, step.y]);
+        }
+
+        assert!(values.iter().all(|v| v.is_finite()), "non-finite value in {:?}", values);
+    }
+
+    // under strict, reading a view before its geometry is set is misuse and panics instead
+    #[cfg(not(feature = "strict"))]
+    #[test]
+    fn accessors_are_finite_before_geometry_is_set() {
+        let mut view = Scrollview::new();
+        view.set_clock(Box::new(MockClock::new(1.0)));
+
+        assert_accessors_finite(&view);
+        assert_eq!(view.position_report(Axis::Vertical).fraction, 0.0);
+        assert_eq!(view.scroll_extent(Axis::Vertical), 0.0);
+    }
+
+    #[test]
+    fn zero_sized_geometry_stays_finite_through_a_fling() {
+        let sizes = [
+            (0.0, 0.0, 0.0, 0.0),
+            (0.0, 0.0, 1000.0, 1000.0),
+            (10000.0, 1000.0, 0.0, 0.0),
+        ];
+
+        for (content_height, content_width, viewport_height, viewport_width) in sizes {
+            let mut view = view();
+            view.set_geometry(content_height, content_width, viewport_height, viewport_width);
+            assert_accessors_finite(&view);
+
+            let time = drag(&mut view, 100.0, 20.0, 8.0, 10);
+            view.push_fling_at(time);
+            assert_accessors_finite(&view);
+
+            let time = settle(&mut view, time);
+            view.step_frame(time + 8.0);
+            assert_accessors_finite(&view);
+        }
+    }
+
+    #[test]
+    fn empty_axis_rubber_bands_only_when_enabled() {
+        for enabled in [true, false] {
+            let mut view = view();
+            view.set_source(Source::Touchscreen);
+            view.set_geometry(1000.0, 1000.0, 1000.0, 1000.0);
+            view.set_empty_overscroll(enabled);
+
+            let time = drag(&mut view, 100.0, 20.0, 8.0, 10);
+            let pulled = view.get_pos_y();
+            assert_eq!(pulled != 0.0, enabled, "pulled to {} with empty overscroll {}", pulled, enabled);
+            assert_eq!(view.position_range(Axis::Vertical) == (0.0, 0.0), !enabled);
+
+            view.push_fling_at(time);
+            settle(&mut view, time);
+            assert!(view.get_pos_y().abs() < 1.0, "rested at {}", view.get_pos_y());
+        }
+    }
+
+    #[test]
+    fn flings_carry_on_with_a_prediction_lead() {
+        let mut rests = Vec::new();
+        for lead in [0.0, 16.0] {
+            let mut view = view();
+            view.set_next_frame_predict(lead);
+
+            let time = drag(&mut view, 100.0, 20.0, 8.0, 10);
+            let released_at = view.get_pos_y();
+            view.push_fling_at(time);
+            view.step_frame(time + 8.0);
+            view.step_frame(time + 16.0);
+            assert!(view.get_pos_y() > released_at, "fling with a {}ms lead didn't move", lead);
+
+            settle(&mut view, time + 16.0);
+            rests.push(view.get_pos_y());
+        }
+
+        // the lead only shows the fling sooner, so it comes to rest in the same place
+        assert!((rests[0] - rests[1]).abs() < 1.0, "came to rest at {:?}", rests);
+    }
+
+    #[test]
+    fn interrupt_freezes_where_content_was_drawn() {
+        let mut view = view();
+        view.set_source(Source::Touchscreen);
+        view.set_geometry(1600.0, 1000.0, 1000.0, 1000.0);
+        view.set_next_frame_predict(16.0);
+
+        // fling out past the end, and catch it while it's still overshooting
+        let mut time = drag(&mut view, 100.0, 20.0, 8.0, 6);
+        view.push_fling_at(time);
+        for _ in 0..2 {
+            time += 8.0;
+            view.step_frame(time);
+        }
+        assert!(view.position_report(Axis::Vertical).overscroll > 0.0);
+        let drawn = view.get_pos_y();
+
+        // touching down part way to the next frame
+        view.push_interrupt_at(time + 4.0);
+        for _ in 0..3 {
+            time += 8.0;
+            view.step_frame(time);
+            assert_eq!(view.get_pos_y(), drawn);
+        }
+    }
+
+    #[test]
+    fn friction_flings_stop_at_v_squared_over_two_decel() {
+        for deceleration in [2000.0, 8000.0] {
+            let mut view = view();
+            view.set_geometry(1_000_000.0, 1000.0, 1000.0, 1000.0);
+            view.set_fling_model(FlingModel::Friction { deceleration });
+
+            let mut time = drag(&mut view, 100.0, 20.0, 8.0, 10);
+            view.push_fling_at(time);
+            for _ in 0..3 {
+                time += 8.0;
+                view.step_frame(time);
+            }
+
+            // under constant deceleration a frame's average speed is the speed at its midpoint
+            let before = view.get_pos_y();
+            time += 8.0;
+            view.step_frame(time);
+            let after = view.get_pos_y();
+            let speed = (after - before) / 8.0 * 1000.0;
+
+            settle(&mut view, time);
+            let expected = speed * speed / (2.0 * deceleration);
+            let traveled = view.get_pos_y() - (before + after) / 2.0;
+            assert!((traveled - expected).abs() < expected * 0.02, "traveled {} rather than {}", traveled, expected);
+        }
+    }
+
+    #[test]
+    fn unfreezing_resumes_the_same_trajectory() {
+        let mut steady = view();
+        let mut paused = view();
+
+        let start = drag(&mut steady, 100.0, 20.0, 8.0, 10);
+        drag(&mut paused, 100.0, 20.0, 8.0, 10);
+        steady.push_fling_at(start);
+        paused.push_fling_at(start);
+
+        let mut time = start;
+        for _ in 0..3 {
+            time += 8.0;
+            steady.step_frame(time);
+            paused.step_frame(time);
+        }
+
+        paused.freeze();
+        let held = paused.get_pos_y();
+        for step in 1..=50 {
+            paused.step_frame(time + step as f64 * 8.0);
+            assert_eq!(paused.get_pos_y(), held);
+            assert!(!paused.animating());
+        }
+        paused.unfreeze();
+
+        // the first frame after unfreezing picks up at the moment of the freeze, as though no
+        // time had passed, and the fling carries on from there
+        let gap = 51.0 * 8.0;
+        paused.step_frame(time + gap);
+        assert_eq!(paused.get_pos_y(), held);
+        for _ in 0..20 {
+            time += 8.0;
+            steady.step_frame(time);
+            paused.step_frame(time + gap);
+            assert!((steady.get_pos_y() - paused.get_pos_y()).abs() < 1e-6, "{} != {}", steady.get_pos_y(), paused.get_pos_y());
+        }
+        assert!(paused.animating());
+    }
+
+    #[test]
+    fn corner_fling_keeps_the_free_axis_going() {
+        let mut view = view();
+        view.set_source(Source::Touchscreen);
+        view.set_geometry(1500.0, 100000.0, 1000.0, 1000.0);
+
+        view.push_interrupt_at(100.0);
+        let mut time = 100.0;
+        for _ in 0..5 {
+            time += 8.0;
+            view.push_pan_at(Axis::Horizontal, 20.0, time);
+            view.push_pan_at(Axis::Vertical, 20.0, time);
+            view.step_frame(time);
+        }
+        view.push_fling_at(time);
+
+        // run until the vertical axis has hit its bound and sprung most of the way back
+        let mut hit = None;
+        for _ in 0..500 {
+            let x = view.get_pos_x();
+            time += 8.0;
+            view.step_frame(time);
+            let overscroll = view.position_report(Axis::Vertical).overscroll;
+            if overscroll > 0.0 && hit.is_none() {
+                hit = Some(view.get_pos_x());
+            }
+            if hit.is_some() {
+                assert!(view.get_pos_x() > x, "horizontal fling stopped at {} with the vertical bound", x);
+            }
+            if hit.is_some() && overscroll < 1.0 {
+                break;
+            }
+        }
+
+        let hit = hit.expect("vertical axis never reached its bound");
+        assert!(view.get_pos_x() - hit > 100.0, "only went {} further", view.get_pos_x() - hit);
+
+        settle(&mut view, time);
+        assert!((view.get_pos_y() - 500.0).abs() < 1.0, "rested at {}", view.get_pos_y());
+        assert!(view.get_pos_x() > hit);
+    }
+
+    #[test]
+    fn tracked_position_trails_by_the_lead() {
+        // a lead of more than one frame has every sample integrate past the time it's for
+        for lead in [4.0, 16.0, 40.0] {
+            let mut view = view();
+            view.set_next_frame_predict(lead);
+
+            let mut time = drag(&mut view, 100.0, 4.0, 8.0, 10);
+            view.push_fling_at(time);
+            for _ in 0..4 {
+                let before = view.get_pos_y();
+                time += 8.0;
+                view.step_frame(time);
+                let speed = (view.get_pos_y() - before) / 8.0;
+
+                let report = view.position_report(Axis::Vertical);
+                let ahead = report.predicted - report.tracked;
+                assert!((ahead - speed * lead).abs() < speed * lead * 0.05, "{}ms lead put tracked {} behind", lead, ahead);
+            }
+        }
+    }
+
+    #[test]
+    fn fast_flings_are_predicted_less_far_ahead() {
+        // how far ahead of the tracked position a fling is drawn, once it's going steadily
+        let overshoot = |delta: f64| {
+            let mut view = view();
+            view.set_next_frame_predict(16.0);
+            view.set_prediction_velocity_rolloff(2.0);
+
+            let mut time = drag(&mut view, 100.0, delta, 8.0, 10);
+            view.push_fling_at(time);
+            for _ in 0..4 {
+                time += 8.0;
+                view.step_frame(time);
+            }
+            let report = view.position_report(Axis::Vertical);
+            (report.predicted - report.tracked, view.effective_lead_time())
+        };
+
+        // well under the rolloff, prediction leads the full 16ms
+        let (slow, slow_lead) = overshoot(1.0);
+        assert_eq!(slow_lead, 16.0);
+
+        // far over it, the lead shrinks to hold the overshoot at what it is at 2px/ms
+        let (fast, fast_lead) = overshoot(20.0);
+        assert!(fast_lead < 16.0);
+        assert!((fast - 2.0 * 16.0).abs() < 2.0, "led {} ahead", fast);
+        assert!(fast > slow);
     }
 
-    /// Set what device type is going to be providing any events that follow until the next source
-    /// is declared
-    pub fn set_source(&mut self, source: Source) {
-        self.current_source = source;
-        self.x.set_source(source);
-        self.y.set_source(source);
+    #[test]
+    fn repeated_interrupts_leave_the_next_fling_alone() {
+        let fling = |interrupts: usize| {
+            let mut view = view();
+            for i in 0..interrupts {
+                view.push_interrupt_at(90.0 + i as f64);
+            }
+            let time = drag(&mut view, 100.0, 20.0, 8.0, 10);
+            let released_at = view.get_pos_y();
+            view.push_fling_at(time);
+            settle(&mut view, time);
+            view.get_pos_y() - released_at
+        };
+
+        let once = fling(0);
+        assert!(once > 100.0, "only flung {}", once);
+        assert_eq!(fling(5), once);
     }
 
-    fn update_config() {
-        let mut config_struct = CONFIG.write().expect("Couldn't lock config struct");
+    #[test]
+    fn insets_extend_the_bounds_on_their_own_side() {
+        // top, bottom, left, right, and the bounds each should give along its axis
+        let cases = [
+            ((50.0, 0.0, 0.0, 0.0), Axis::Vertical, (-50.0, 9000.0)),
+            ((0.0, 50.0, 0.0, 0.0), Axis::Vertical, (0.0, 9050.0)),
+            ((0.0, 0.0, 50.0, 0.0), Axis::Horizontal, (-50.0, 9000.0)),
+            ((0.0, 0.0, 0.0, 50.0), Axis::Horizontal, (0.0, 9050.0)),
+        ];
 
-        let mut config_dir: std::path::PathBuf = app_root(AppDataType::UserConfig, &APP_INFO).unwrap(); // TODO: gracefully handle
-        let config_file = std::path::PathBuf::from("config.ini");
-        config_dir.push(config_file);
+        for ((top, bottom, left, right), axis, (lower, upper)) in cases {
+            let mut view = view();
+            view.set_geometry(10000.0, 10000.0, 1000.0, 1000.0);
+            view.set_content_insets(top, bottom, left, right);
+            assert_eq!(view.position_range(axis), (lower, upper));
+            assert_eq!(view.scroll_extent(axis), upper - lower);
 
-        let _ = Ini::from_file(&config_dir).map(|config| {
-            println!("Found config file, applying...");
-            config.get("config", "event_expiry_count").map(|v: usize| { config_struct.EVENT_EXPIRY_COUNT = v});
-            config.get("config", "sample_expiry_count").map(|v: usize| { config_struct.SAMPLE_EXPIRY_COUNT = v});
+            // flung hard toward each end, content comes to rest at the extended bound
+            let mut time = 100.0;
+            for (delta, bound) in [(-200.0, lower), (200.0, upper)] {
+                view.push_interrupt_at(time);
+                for _ in 0..10 {
+                    time += 8.0;
+                    view.push_pan_at(axis, delta, time);
+                    view.step_frame(time);
+                }
+                view.push_fling_at(time);
+                time = settle(&mut view, time);
 
-            config.get("config", "ticks_to_coast").map(|v: f64| { config_struct.TICKS_TO_COAST = v});
-            config.get("config", "timestep").map(|v: f64| { config_struct.TIMESTEP = v});
-            config.get("config", "min_velocity_to_idle").map(|v: f64| { config_struct.MIN_VELOCITY_TO_IDLE = v});
-            config.get("config", "post_acceleration_scale_velocity").map(|v: f64| { config_struct.POST_ACCEL_SCALE_VELOCITY = v});
-            config.get("config", "pre_acceleration_scale_velocity").map(|v: f64| { config_struct.PRE_ACCEL_SCALE_VELOCITY = v});
-            config.get("config", "shift_window_ms").map(|v: f64| { config_struct.SHIFT_WINDOW_MS = v});
-            config.get("config", "overscroll_elasticity_coefficient").map(|v: f64| { config_struct.OVERSCROLL_ELASTICITY_COEFFICIENT = v});
-            config.get("config", "content_mass_value").map(|v: f64| { config_struct.CONTENT_MASS_VALUE = v});
-            config.get("config", "overscroll_spring_constant").map(|v: f64| { config_struct.OVERSCROLL_SPRING_CONSTANT = v});
-            config.get("config", "bounce_damping_factor").map(|v: f64| { config_struct.BOUNCE_DAMP_FACTOR = v});
-            config.get("config", "zero_delta_injection_wait_ms").map(|v: f64| { config_struct.MAX_MS_WITHOUT_ZERO_INJECTION = v});
-            config.get("config", "first_event_multiplier").map(|v: f64| { config_struct.MULTIPLY_FIRST_EVENT = v});
-            config.get("config", "accel_decel_descriminant").map(|v: f64| { config_struct.ACCEL_DECEL_DESCRIMINANT = v});
-            config.get("config", "acceleration_exponent").map(|v: f64| { config_struct.ACCELERATION_EXPONENT = v});
-            config.get("config", "fling_boost_constant_factor").map(|v: f64| { config_struct.FLING_BOOST_CONSTANT_FACTOR = v});
+                let position = view.position_report(axis).predicted;
+                assert!((position - bound).abs() < 0.5, "rested at {} rather than {}", position, bound);
+            }
+        }
+    }
 
-            config.get("config", "flips_until_idle").map(|v: u64| { config_struct.FLIPS_TO_IDLE = v});
-        }).map_err(|_| {
-            println!("Couldn't find config file");
-            // maybe add message if can't be found?
-        });
+    #[test]
+    fn same_direction_flings_build_on_each_other() {
+        // speed a few frames into a second fling, flicked while the first is still going
+        let second_fling_speed = |inherit: bool, direction: f64| {
+            let mut view = view();
+            view.set_geometry(1_000_000.0, 1000.0, 1000.0, 1000.0);
+            view.set_fling_inheritance(inherit);
+
+            let mut time = drag(&mut view, 100.0, 20.0, 8.0, 10);
+            view.push_fling_at(time);
+            for _ in 0..10 {
+                time += 8.0;
+                view.step_frame(time);
+            }
+
+            let mut time = drag(&mut view, time, 20.0 * direction, 8.0, 10);
+            view.push_fling_at(time);
+            for _ in 0..3 {
+                time += 8.0;
+                view.step_frame(time);
+            }
+            let before = view.get_pos_y();
+            view.step_frame(time + 8.0);
+            (view.get_pos_y() - before) / 8.0
+        };
+
+        let replaced = second_fling_speed(false, 1.0);
+        let combined = second_fling_speed(true, 1.0);
+        assert!(combined > replaced * 1.2, "{} px/ms inherited against {} replaced", combined, replaced);
+
+        // the other way, the first fling is dropped either way
+        assert_eq!(second_fling_speed(true, -1.0), second_fling_speed(false, -1.0));
+    }
+
+    #[test]
+    fn no_fling_from_fingers_lifting_after_a_pinch() {
+        // how far content moves from the end of a pinch, through fingers drifting as they lift
+        let drift = |lockout: f64| {
+            let mut view = view();
+            view.set_post_zoom_fling_lockout_ms(lockout);
+
+            let mut time = drag(&mut view, 100.0, 20.0, 8.0, 10);
+            view.push_zoom_end(time);
+            let zoomed_at = view.get_pos_y();
+            for _ in 0..3 {
+                time += 8.0;
+                view.push_pan_at(Axis::Vertical, 20.0, time);
+                view.step_frame(time);
+            }
+            view.push_fling_at(time);
+            settle(&mut view, time);
+            view.get_pos_y() - zoomed_at
+        };
+
+        assert!(drift(0.0) > 100.0);
+        assert_eq!(drift(100.0), 0.0);
+    }
+
+    #[test]
+    fn slow_scrolls_stay_smooth_ten_million_px_out() {
+        // per-frame movement of a slow drag started from offset
+        let increments = |offset: f64| {
+            let mut view = view();
+            view.set_geometry(20_000_000.0, 1000.0, 1000.0, 1000.0);
+            view.step_frame(90.0);
+            view.adjust_position(AxisVector { y: offset, ..Default::default() });
+            view.step_frame(95.0);
+
+            let mut steps = Vec::new();
+            let mut time = 100.0;
+            view.push_interrupt_at(time);
+            for _ in 0..30 {
+                let before = view.render_transform();
+                time += 8.0;
+                view.push_pan_at(Axis::Vertical, 0.1, time);
+                view.step_frame(time);
+                let after = view.render_transform();
+
+                // the split parts reassemble the translation, with the fraction kept in f32
+                let ((_, whole), (_, fraction)) = after.translate_split();
+                assert!((whole as f64 + fraction as f64 - after.translate_y).abs() < 1e-6);
+
+                steps.push(before.translate_y - after.translate_y);
+            }
+            assert!(view.get_pos_y() >= offset);
+            steps
+        };
+
+        let near = increments(0.0);
+        let far = increments(10_000_000.0);
+        assert!(near.iter().skip(2).all(|&step| step > 0.0 && step < 1.0), "{:?}", near);
+        for (near, far) in near.iter().zip(&far) {
+            assert!((near - far).abs() < 1e-6, "moved {} out there against {} near the origin", far, near);
+        }
+    }
+
+    #[test]
+    fn initial_pan_ramp_eases_in_a_coalesced_first_delta() {
+        // position after each frame of a drag opening with one large coalesced delta
+        let positions = |ramp: f64| {
+            let mut view = view();
+            view.set_initial_pan_ramp(ramp);
+
+            let mut time = 100.0;
+            view.push_interrupt_at(time);
+            let mut positions = Vec::new();
+            for delta in [200.0, 20.0, 20.0, 20.0, 20.0, 20.0, 20.0, 20.0, 20.0, 20.0] {
+                time += 8.0;
+                view.push_pan_at(Axis::Vertical, delta, time);
+                view.step_frame(time);
+                positions.push(view.get_pos_y());
+            }
+            positions
+        };
+
+        let sudden = positions(0.0);
+        let eased = positions(40.0);
+        assert!(eased[2] < sudden[2] * 0.75, "moved {} with the ramp against {} without", eased[2], sudden[2]);
+
+        // once the ramp is over, pans move by their full delta again
+        let step = |p: &Vec<f64>| p[9] - p[8];
+        assert!((step(&eased) - step(&sudden)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fling_carries_on_into_content_loaded_mid_bounce() {
+        let mut view = view();
+        view.set_source(Source::Touchscreen);
+        view.set_geometry(1600.0, 1000.0, 1000.0, 1000.0);
+
+        let mut time = drag(&mut view, 100.0, 20.0, 8.0, 6);
+        view.push_fling_at(time);
+        while view.position_report(Axis::Vertical).overscroll <= 0.0 {
+            time += 8.0;
+            view.step_frame(time);
+        }
+
+        // more content loads while the fling is out past the old bottom
+        view.set_geometry(100_000.0, 1000.0, 1000.0, 1000.0);
+        let mut last_step = f64::INFINITY;
+        let mut position = view.get_pos_y();
+        while view.animating() {
+            time += 8.0;
+            view.step_frame(time);
+            let step = view.get_pos_y() - position;
+            position = view.get_pos_y();
+
+            // carrying on forward and slowing down, with no spring pulling it either way
+            assert!(step >= 0.0, "turned back by {}", step);
+            assert!(step <= last_step + 1e-9, "sped up from {} to {}", last_step, step);
+            last_step = step;
+        }
+        assert!(position > 700.0, "stopped short at {}", position);
+        assert_eq!(view.position_report(Axis::Vertical).overscroll, 0.0);
+    }
+
+    #[test]
+    fn reflow_keeps_the_anchored_content_in_place() {
+        for anchor in [0.0, 0.5, 1.0] {
+            let mut view = view();
+            let time = drag(&mut view, 100.0, 20.0, 8.0, 10);
+            let time = settle(&mut view, time);
+
+            let before = view.get_pos_y() + 1000.0 * anchor;
+            let fraction = before / 10000.0;
+
+            // fonts grow, and the document reflows half as long again
+            view.set_content_size_anchored(15000.0, 1000.0, anchor);
+            view.step_frame(time + 8.0);
+
+            let after = view.get_pos_y() + 1000.0 * anchor;
+            assert!((after / 15000.0 - fraction).abs() < 1e-9, "anchor moved from {} to {} of the content", fraction, after / 15000.0);
+            assert!(!view.animating());
+        }
+    }
+
+    #[test]
+    fn jerk_limit_holds_through_spring_back_and_snap() {
+        // positions over each 1ms frame from just before letting go at rest, either 80px past
+        // the top or 49px short of a grid boundary, until the spring has come to rest
+        fn spring(limit: f64, snap: bool) -> Vec<f64> {
+            let mut view = view();
+            view.set_source(Source::Touchscreen);
+            view.set_jerk_limit(limit);
+            let mut time = if snap {
+                view.set_grid_snap(Axis::Vertical, 100.0, 0.0);
+                drag(&mut view, 100.0, 10.0, 8.0, 4) + 300.0
+            } else {
+                drag(&mut view, 100.0, -20.0, 8.0, 5) + 300.0
+            };
+
+            let mut positions = Vec::new();
+            for _ in 0..3 {
+                time += 1.0;
+                view.step_frame(time);
+                positions.push(view.get_pos_y());
+            }
+            view.push_release_at(time);
+            while view.animating() {
+                time += 1.0;
+                view.step_frame(time);
+                positions.push(view.get_pos_y());
+                assert!(positions.len() < 5000, "never came to rest");
+            }
+            positions
+        }
+
+        let jerk = |positions: &[f64]| {
+            positions.windows(4).map(|w| w[3] - 3.0 * w[2] + 3.0 * w[1] - w[0]).collect::<Vec<_>>()
+        };
+
+        let limit = 0.0001;
+        for (snap, rest) in [(false, 0.0), (true, 200.0)] {
+            let eased = spring(limit, snap);
+            assert_eq!(*eased.last().unwrap(), rest);
+
+            // the whole way, from coming on to landing. The spring is integrated in steps
+            // finer than the frames and has its jerk measured here from third differences of
+            // frame positions, so allow 2% for the two not lining up exactly
+            let eased_jerk = jerk(&eased);
+            assert!(eased_jerk.iter().all(|j| j.abs() <= limit * 1.02), "snap {}: {:?}", snap, eased_jerk);
+
+            let unlimited = spring(0.0, snap);
+            assert_eq!(*unlimited.last().unwrap(), rest);
+            assert!(unlimited.len() < eased.len());
+            assert!(jerk(&unlimited)[..3].iter().any(|j| j.abs() > limit * 100.0), "snap {}", snap);
+        }
+    }
+
+    #[test]
+    fn stale_pan_input_ages_out_without_a_fling() {
+        let mut stalled = view();
+        stalled.set_max_sample_age_ms(100.0);
+
+        // input stops mid-drag with no interrupt, and frames carry on for half a second
+        let mut time = drag(&mut stalled, 100.0, 20.0, 8.0, 10);
+        for _ in 0..60 {
+            time += 8.0;
+            stalled.step_frame(time);
+        }
+        assert!(!stalled.animating());
+        let stopped = stalled.get_pos_y();
+
+        stalled.push_fling_at(time);
+        settle(&mut stalled, time);
+        assert_eq!(stalled.get_pos_y(), stopped);
+
+        // with the app backgrounded, frames stop too, and input resuming afterward carries on
+        // from where it left off just as if nothing had been dropped
+        let resumed = |max_age: Option<f64>| {
+            let mut view = view();
+            if let Some(age) = max_age {
+                view.set_max_sample_age_ms(age);
+            }
+
+            let mut time = drag(&mut view, 100.0, 20.0, 8.0, 10) + 500.0;
+            (0..3)
+                .map(|_| {
+                    view.push_pan_at(Axis::Vertical, 1.0, time);
+                    view.step_frame(time);
+                    time += 8.0;
+                    view.get_pos_y()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(resumed(Some(100.0)), resumed(None));
+    }
+
+    #[test]
+    fn scroll_anchor_stays_put_as_content_above_grows() {
+        let mut view = view();
+        let time = drag(&mut view, 100.0, 20.0, 8.0, 10);
+        let time = settle(&mut view, time);
+        let scrolled = view.get_pos_y();
+
+        // an element a little way into the viewport, below one that then loads in taller
+        let anchor_top = scrolled + 300.0;
+        view.set_scroll_anchor(anchor_top, 7);
+        view.notify_anchor_moved(7, anchor_top + 250.0);
+        assert_eq!(view.get_pos_y(), scrolled + 250.0);
+        view.step_frame(time + 8.0);
+        assert_eq!(view.get_pos_y(), scrolled + 250.0);
+
+        // other elements moving don't shift anything, and the anchor's next move is measured
+        // from where it was last reported
+        view.notify_anchor_moved(8, anchor_top + 1000.0);
+        assert_eq!(view.get_pos_y(), scrolled + 250.0);
+        view.notify_anchor_moved(7, anchor_top + 200.0);
+        assert_eq!(view.get_pos_y(), scrolled + 200.0);
+    }
+
+    #[test]
+    fn outward_fling_from_an_overscroll_is_held_close() {
+        let mut view = view();
+        view.set_source(Source::Touchscreen);
+        let time = drag(&mut view, 100.0, 20.0, 8.0, 3);
+        let time = settle(&mut view, time);
+
+        // dragged back up past the top and let go while still pulling outward, which unresisted
+        // would fling on some 18px deeper
+        let mut time = drag(&mut view, time, -20.0, 8.0, 5);
+        let released = view.get_pos_y();
+        assert!(released < 0.0);
+        view.push_fling_at(time);
+
+        let mut deepest = released;
+        while view.animating() {
+            time += 8.0;
+            view.step_frame(time);
+            deepest = deepest.min(view.get_pos_y());
+        }
+        assert!(released - deepest < 2.0, "flung {} further out", released - deepest);
+        assert_eq!(view.get_pos_y(), 0.0);
+    }
+
+    #[test]
+    fn overscroll_differs_between_drag_and_fling() {
+        fn tuned(tune: fn(&mut Scrollview)) -> Scrollview {
+            let mut view = view();
+            view.set_source(Source::Touchscreen);
+            tune(&mut view);
+            view
+        }
+        // how far past the top a drag up from the top pulls the content
+        fn dragged(tune: fn(&mut Scrollview)) -> f64 {
+            let mut view = tuned(tune);
+            drag(&mut view, 100.0, -20.0, 8.0, 5);
+            -view.get_pos_y()
+        }
+        // and how far past it a fling up from 300px down carries it
+        fn flung(tune: fn(&mut Scrollview)) -> f64 {
+            let mut view = tuned(tune);
+            let time = drag(&mut view, 100.0, 20.0, 8.0, 3);
+            let time = settle(&mut view, time);
+            let mut time = drag(&mut view, time, -10.0, 8.0, 3);
+            view.push_fling_at(time);
+
+            let mut deepest: f64 = 0.0;
+            while view.animating() {
+                time += 8.0;
+                view.step_frame(time);
+                deepest = deepest.min(view.get_pos_y());
+            }
+            -deepest
+        }
+
+        let stock = dragged(|_| {});
+        assert!(dragged(|view| view.set_drag_overscroll_resistance(0.1)) > stock + 10.0);
+        assert!(dragged(|view| view.set_drag_overscroll_resistance(10.0)) < stock);
+        assert_eq!(dragged(|view| view.set_fling_overscroll_limit(10.0)), stock);
+
+        assert!(flung(|_| {}) > 50.0);
+        let limited = flung(|view| view.set_fling_overscroll_limit(10.0));
+        assert!(limited > 9.0 && limited <= 10.0, "flung {} past the top", limited);
+    }
+
+    #[test]
+    fn extent_scaled_flings_go_further_through_longer_content() {
+        // how far a gentle flick carries through content of the given height
+        let fling = |content_height: f64, scaled: bool| {
+            let mut view = view();
+            view.set_geometry(content_height, 1000.0, 1000.0, 1000.0);
+            view.set_fling_scale_by_extent(scaled);
+
+            let time = drag(&mut view, 100.0, 1.0, 8.0, 5);
+            let released = view.get_pos_y();
+            view.push_fling_at(time);
+            settle(&mut view, time);
+            view.get_pos_y() - released
+        };
+
+        // unscaled, the same flick goes the same distance whatever the length
+        let unscaled = fling(3000.0, false);
+        assert_eq!(fling(17000.0, false), unscaled);
+
+        // scaled, it's left alone at a 4000px extent and goes further the longer the content
+        assert_eq!(fling(5000.0, true), unscaled);
+        let (short, long) = (fling(3000.0, true), fling(17000.0, true));
+        assert!(short < unscaled * 0.5, "went {} through short content", short);
+        assert!(long > unscaled * 4.0, "went {} through long content", long);
+    }
+
+    #[test]
+    fn release_after_holding_still_settles_in_place() {
+        // a fast drag held still for 300ms (frames still coming) before the finger lifts,
+        // giving where it stopped and the view just after the release
+        let held = |snap: f64| {
+            let mut view = view();
+            view.set_grid_snap(Axis::Vertical, snap, 0.0);
+            let mut time = drag(&mut view, 100.0, 20.0, 8.0, 10);
+            for _ in 0..40 {
+                time += 8.0;
+                view.step_frame(time);
+            }
+            let stopped = view.get_pos_y();
+            view.push_fling_at(time);
+            view.step_frame(time + 8.0);
+            (stopped, view, time + 8.0)
+        };
+
+        let (stopped, view, _) = held(0.0);
+        assert!(!view.animating());
+        assert_eq!(view.get_pos_y(), stopped);
+
+        // off the grid it springs in to the nearest cell boundary, not on past it
+        let (stopped, mut view, time) = held(100.0);
+        assert!((stopped - 830.0).abs() < 1.0, "stopped at {}", stopped);
+        assert!(view.get_pos_y() <= stopped);
+        settle(&mut view, time);
+        assert_eq!(view.get_pos_y(), 800.0);
+    }
+
+    #[test]
+    fn arrested_fling_eases_to_a_stop_in_time() {
+        // frame-by-frame movement of a fling from 32ms after release, arrested then if asked
+        let fling = |arrest: Option<f64>| {
+            let mut view = view();
+            let mut time = drag(&mut view, 100.0, 4.0, 8.0, 10);
+            view.push_fling_at(time);
+            for _ in 0..4 {
+                time += 8.0;
+                view.step_frame(time);
+            }
+            if let Some(ms) = arrest {
+                view.arrest_fling(ms);
+            }
+
+            let mut moves = vec![];
+            while view.animating() && moves.len() < 50 {
+                let before = view.get_pos_y();
+                time += 8.0;
+                view.step_frame(time);
+                moves.push(view.get_pos_y() - before);
+            }
+            moves
+        };
+
+        let natural = fling(None);
+        let arrested = fling(Some(100.0));
+
+        // stopped within the 100ms, to the frame, where it would otherwise still be going
+        assert!(arrested.len() <= 13, "{:?}", arrested);
+        assert_eq!(*arrested.last().unwrap(), 0.0);
+        assert!(natural[12] > 1.0, "{:?}", natural);
+
+        // slowing all the way, from close to the speed it was going rather than dead
+        assert!(arrested[0] > natural[0] * 0.75, "{:?}", arrested);
+        assert!(arrested.windows(2).all(|w| w[1] < w[0]), "{:?}", arrested);
+    }
+
+    #[test]
+    fn deltas_and_positions_share_one_convention() {
+        // positive deltas scroll toward the end of the content, growing the position
+        let mut forward = view();
+        forward.set_source(Source::Touchscreen);
+        let time = drag(&mut forward, 100.0, 20.0, 8.0, 3);
+        let time = settle(&mut forward, time);
+        let scrolled = forward.get_pos_y();
+        assert!(scrolled > 0.0);
+
+        // given as content movement, deltas the other way round scroll just the same
+        let mut content = view();
+        content.set_source(Source::Touchscreen);
+        content.set_pan_direction(ScrollDirection::Content);
+        let t = drag(&mut content, 100.0, -20.0, 8.0, 3);
+        settle(&mut content, t);
+        assert_eq!(content.get_pos_y(), scrolled);
+
+        // past the start, position and overscroll both go negative
+        drag(&mut forward, time, -20.0, 8.0, 20);
+        let report = forward.position_report(Axis::Vertical);
+        assert!(report.predicted < 0.0);
+        assert_eq!(report.overscroll, report.predicted);
+        assert_eq!(report.fraction, 0.0);
+
+        // and a pan cut short at a hard bound gives back what's left the way it was pushed
+        let mut hard = view();
+        hard.set_pan_direction(ScrollDirection::Content);
+        let t = drag(&mut hard, 100.0, -5.0, 8.0, 3);
+        let t = settle(&mut hard, t);
+        let from = hard.get_pos_y();
+        assert!(from > 0.0);
+        hard.push_interrupt_at(t + 8.0);
+        match hard.push_pan_at(Axis::Vertical, 1000.0, t + 16.0) {
+            PanOutcome::PartiallyConsumed { remaining } => assert!((remaining - (1000.0 - from)).abs() < 1e-6, "{}", remaining),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn settled_flings_rest_exactly_on_the_bound() {
+        // with a prediction lead, so the flings are drawn led ahead until they stop
+        for (delta, rest) in [(20.0, Some(9000.0)), (8.0, None)] {
+            let mut view = view();
+            view.set_source(Source::Touchscreen);
+            view.set_next_frame_predict(16.0);
+            let time = drag(&mut view, 100.0, delta, 8.0, 10);
+            view.push_fling_at(time);
+            let mut time = settle(&mut view, time);
+
+            let settled = view.get_position_absolute().y;
+            if let Some(rest) = rest {
+                assert_eq!(settled, rest);
+            }
+            let report = view.position_report(Axis::Vertical);
+            assert_eq!(report.predicted, report.tracked);
+
+            // and it stays there to the pixel frame after frame
+            for _ in 0..10 {
+                time += 8.0;
+                view.step_frame(time);
+                assert_eq!(view.get_position_absolute().y, settled);
+            }
+        }
+    }
+
+    #[test]
+    fn exclusive_axis_lock_gives_the_whole_gesture_to_one_axis() {
+        // a drag moving (x, y) by first in its first 8ms and by rest in each 8ms after, flung
+        let gesture = |view: &mut Scrollview, start: f64, first: (f64, f64), rest: (f64, f64)| {
+            view.push_interrupt_at(start);
+            let mut time = start;
+            for step in 0..10 {
+                time += 8.0;
+                let (x, y) = if step == 0 { first } else { rest };
+                view.push_pan_at(Axis::Horizontal, x, time);
+                view.push_pan_at(Axis::Vertical, y, time);
+                view.step_frame(time);
+            }
+            view.push_fling_at(time);
+            settle(view, time)
+        };
+
+        let scrollable = || {
+            let mut view = view();
+            view.set_geometry(10000.0, 10000.0, 1000.0, 1000.0);
+            view
+        };
+
+        let mut free = scrollable();
+        // starting vertically, then going mostly sideways
+        gesture(&mut free, 100.0, (0.0, 5.0), (20.0, 2.0));
+        assert!(free.get_pos_x() > 0.0 && free.get_pos_y() > 0.0);
+
+        let mut exclusive = scrollable();
+        exclusive.set_axis_lock(AxisLockMode::Exclusive);
+        let time = gesture(&mut exclusive, 100.0, (0.0, 5.0), (20.0, 2.0));
+        assert_eq!(exclusive.get_pos_x(), 0.0);
+        assert!(exclusive.get_pos_y() > free.get_pos_y());
+
+        // the claim lasts the gesture only, so the next can go to the other axis
+        let scrolled = exclusive.get_pos_y();
+        gesture(&mut exclusive, time + 8.0, (5.0, 0.0), (5.0, 5.0));
+        assert!(exclusive.get_pos_x() > 0.0);
+        assert_eq!(exclusive.get_pos_y(), scrolled);
+    }
+
+    #[test]
+    fn fling_eases_home_when_the_content_shrinks_to_fit() {
+        // positions each frame from a fling whose content shrinks to the viewport 32ms in
+        let shrunk = |duration: Option<f64>| {
+            let mut view = view();
+            if let Some(ms) = duration {
+                view.set_collapse_duration(ms);
+            }
+            let mut time = drag(&mut view, 100.0, 10.0, 8.0, 10);
+            view.push_fling_at(time);
+            for _ in 0..4 {
+                time += 8.0;
+                view.step_frame(time);
+            }
+            view.set_geometry(1000.0, 1000.0, 1000.0, 1000.0);
+
+            let mut positions = vec![view.get_pos_y()];
+            while view.animating() && positions.len() < 100 {
+                time += 8.0;
+                view.step_frame(time);
+                positions.push(view.get_pos_y());
+            }
+            positions
+        };
+
+        // back to 0 over the default 300ms, turning around gently rather than dead, and never
+        // past it
+        let eased = shrunk(None);
+        assert!(eased.len() <= 40, "{:?}", eased);
+        assert_eq!(*eased.last().unwrap(), 0.0);
+        assert!(eased[0] - eased[1] < 2.0, "{:?}", eased);
+        assert!(eased.windows(2).all(|w| w[1] <= w[0] && w[1] >= 0.0), "{:?}", eased);
+
+        assert_eq!(shrunk(Some(0.0)), vec![eased[0], 0.0]);
+    }
+
+    #[test]
+    fn frametime_change_mid_fling_leaves_the_view_at_rest() {
+        let mut flinging = view();
+        flinging.set_next_frame_predict(16.0);
+        let mut time = drag(&mut flinging, 100.0, 10.0, 8.0, 10);
+        flinging.push_fling_at(time);
+        for _ in 0..4 {
+            time += 8.0;
+            flinging.step_frame(time);
+        }
+        let shown = flinging.get_pos_y();
+        assert!(flinging.animating());
+
+        flinging.set_avg_frametime(1000.0 / 60.0);
+        assert!(!flinging.animating());
+        let report = flinging.position_report(Axis::Vertical);
+        assert_eq!((report.predicted, report.tracked), (shown, shown));
+        for _ in 0..10 {
+            time += 16.0;
+            flinging.step_frame(time);
+            assert_eq!(flinging.get_pos_y(), shown);
+        }
+
+        // and a drag and fling afterward go just as they would on a view that had never moved
+        let mut fresh = view();
+        fresh.set_next_frame_predict(16.0);
+        fresh.set_avg_frametime(1000.0 / 60.0);
+        let after = |view: &mut Scrollview, start: f64| {
+            let from = view.get_pos_y();
+            let time = drag(view, start, 1.0, 16.0, 3);
+            let dragged = view.get_pos_y() - from;
+            view.push_fling_at(time);
+            settle(view, time);
+            (dragged, view.get_pos_y() - from)
+        };
+        let (dragged, flung) = after(&mut flinging, time);
+        let (fresh_dragged, fresh_flung) = after(&mut fresh, 100.0);
+        assert!((dragged - fresh_dragged).abs() < fresh_dragged * 0.01, "dragged {} against {}", dragged, fresh_dragged);
+        assert!((flung - fresh_flung).abs() < fresh_flung * 0.01, "flung {} against {}", flung, fresh_flung);
+    }
+
+    #[test]
+    fn end_bias_picks_a_side_of_the_short_last_cell() {
+        // released at rest at the given position, 3500px of content in cells of a 1000px page,
+        // so the last cell is cut to 500px by the end at 2500; gives where it settles
+        let released_at = |position: f64, bias: Option<EndBias>| {
+            let mut view = view();
+            view.set_geometry(3500.0, 1000.0, 1000.0, 1000.0);
+            view.set_grid_snap(Axis::Vertical, 1000.0, 0.0);
+            if let Some(bias) = bias {
+                view.set_end_bias(Axis::Vertical, bias);
+            }
+            view.step_frame(100.0);
+            view.adjust_position(AxisVector { y: position, ..Default::default() });
+            view.push_interrupt_at(108.0);
+            view.push_release_at(116.0);
+            settle(&mut view, 116.0);
+            view.get_pos_y()
+        };
+
+        // whole cells settle to their nearest boundary whatever the bias
+        for bias in [None, Some(EndBias::FullCell), Some(EndBias::TrueEnd)] {
+            assert_eq!(released_at(1400.0, bias), 1000.0);
+        }
+
+        // in the short one, even close to the end, the default and FullCell show a whole page
+        // and TrueEnd goes on to the end
+        for position in [2100.0, 2400.0] {
+            assert_eq!(released_at(position, None), 2000.0);
+            assert_eq!(released_at(position, Some(EndBias::FullCell)), 2000.0);
+            assert_eq!(released_at(position, Some(EndBias::TrueEnd)), 2500.0);
+        }
+    }
+
+    #[test]
+    fn outlier_rejection_ignores_one_huge_delta() {
+        // a steady drag of 5px every 8ms, with one 500px spike in the middle if asked (or
+        // nothing at all at that time), flung at the end; gives where it was let go and where
+        // it came to rest
+        let drag_with = |spike: Option<f64>, factor: Option<f64>| {
+            let mut view = view();
+            view.set_outlier_rejection(factor);
+            view.push_interrupt_at(100.0);
+            let mut time = 100.0;
+            for step in 0..12 {
+                time += 8.0;
+                let delta = if step == 6 { spike } else { Some(5.0) };
+                if let Some(delta) = delta {
+                    let outcome = view.push_pan_at(Axis::Vertical, delta, time);
+                    assert_eq!(outcome == PanOutcome::Rejected, delta > 5.0 && factor.is_some());
+                }
+                view.step_frame(time);
+                if step == 6 && spike.is_some() {
+                    assert_eq!(view.input_stats().dropped, factor.is_some() as u32);
+                }
+            }
+            let released = view.get_pos_y();
+            view.push_fling_at(time);
+            settle(&mut view, time);
+            (released, view.get_pos_y())
+        };
+
+        // dropped, the spike plays no part in either the drag or the fling after it
+        let clean = drag_with(None, Some(10.0));
+        assert_eq!(drag_with(Some(500.0), Some(10.0)), clean);
+
+        let (released, _) = drag_with(Some(500.0), None);
+        assert!(released > clean.0 + 400.0, "let go at {}", released);
+    }
+
+    #[test]
+    fn pinch_translation_flings_on_with_zoom_pan_momentum() {
+        // a pinch whose center is carried twice as fast down as sideways, the fingers lifting
+        // with a fling pushed inside the lockout if asked; gives how far content moved on each
+        // axis from the end of the pinch
+        let pinch = |momentum: bool, lift_fling: bool| {
+            let mut view = view();
+            view.set_geometry(10000.0, 10000.0, 1000.0, 1000.0);
+            view.set_post_zoom_fling_lockout_ms(100.0);
+            view.set_zoom_pan_momentum(momentum);
+
+            view.push_interrupt_at(100.0);
+            let mut time = 100.0;
+            for _ in 0..10 {
+                time += 8.0;
+                view.push_pan_at(Axis::Horizontal, 2.0, time);
+                view.push_pan_at(Axis::Vertical, 4.0, time);
+                view.step_frame(time);
+            }
+            view.push_zoom_end(time);
+            let (x, y) = (view.get_pos_x(), view.get_pos_y());
+
+            view.step_frame(time + 8.0);
+            if lift_fling {
+                view.push_fling_at(time + 16.0);
+            }
+            settle(&mut view, time + 8.0);
+            (view.get_pos_x() - x, view.get_pos_y() - y)
+        };
+
+        assert_eq!(pinch(false, true), (0.0, 0.0));
+
+        // both axes glide on, the faster one further, and the fling in the lockout neither
+        // stops nor boosts that
+        let (x, y) = pinch(true, true);
+        assert!(x > 100.0 && y > x, "glided ({}, {})", x, y);
+        assert_eq!(pinch(true, false), (x, y));
+    }
+
+    #[test]
+    fn bouncy_springs_always_settle() {
+        // frames a spring back from 80px past the top takes to settle, with damping from the
+        // stock amount down to barely any, and a stiffer spring than usual
+        let settle_frames = |damping: f64, frames: Option<usize>| {
+            let mut view = view();
+            view.set_source(Source::Touchscreen);
+            if let Some(frames) = frames {
+                view.set_settle_frames(frames);
+            }
+            let profile = ScrollProfile {
+                bounce_damping_factor: damping,
+                overscroll_spring_constant: 1.2,
+                ..view.current_profile()
+            };
+            view.apply_profile(&profile);
+
+            let mut time = drag(&mut view, 100.0, -20.0, 8.0, 5);
+            view.push_release_at(time);
+            let mut frames = 0;
+            while view.animating() {
+                assert!(frames < 2000, "still going {}ms on with damping {}", frames * 8, damping);
+                time += 8.0;
+                view.step_frame(time);
+                frames += 1;
+            }
+            assert_eq!(view.get_pos_y(), 0.0);
+            frames
+        };
+
+        for damping in [0.9974, 0.999, 0.9998, 0.99998] {
+            // without the guard, only the slower check for the position holding still ends it
+            let guarded = settle_frames(damping, None);
+            assert!(guarded + 20 <= settle_frames(damping, Some(usize::MAX)), "took {} frames", guarded);
+            assert!(guarded < settle_frames(damping, Some(40)));
+        }
+    }
+
+    #[test]
+    fn snap_magnetism_draws_drags_across_a_boundary() {
+        // positions each frame of a slow, steady drag across 100px cells
+        let drag_over = |strength: f64| {
+            let mut view = view();
+            view.set_source(Source::Touchscreen);
+            view.set_grid_snap(Axis::Vertical, 100.0, 0.0);
+            view.set_snap_magnetism(strength);
+            view.push_interrupt_at(100.0);
+
+            let mut time = 100.0;
+            (0..80)
+                .map(|_| {
+                    time += 8.0;
+                    view.push_pan_at(Axis::Vertical, 2.0, time);
+                    view.step_frame(time);
+                    view.get_pos_y()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let plain = drag_over(0.0);
+        let drawn = drag_over(0.5);
+        let (mut ahead, mut behind) = (0, 0);
+        for (position, drawn) in plain.iter().zip(&drawn) {
+            let pull = drawn - position;
+            let gap = (position / 100.0).round() * 100.0 - position;
+
+            // ahead of the drag coming up on a boundary and behind it going away, only within
+            // a quarter cell, and never by more than strength times a quarter of that
+            if gap.abs() >= 25.0 {
+                assert!(pull.abs() < 1e-9, "pulled {} at {}", pull, position);
+            } else {
+                assert!(pull * gap >= 0.0, "pulled {} at {}", pull, position);
+            }
+            assert!(pull.abs() <= 0.5 * 25.0 / 4.0 + 1e-9);
+
+            ahead += (pull > 2.5) as usize;
+            behind += (pull < -2.5) as usize;
+        }
+        assert!(ahead >= 5 && behind >= 5, "{} ahead, {} behind", ahead, behind);
+    }
+
+    #[test]
+    fn fling_model_swapped_mid_fling_brakes_harder_gradually() {
+        // how far each 8ms frame of a fling moves, switching to the given model 10 frames in
+        let fling = |model: Option<FlingModel>| {
+            let mut view = view();
+            let mut time = drag(&mut view, 100.0, 10.0, 8.0, 10);
+            view.push_fling_at(time);
+            let mut last = view.get_pos_y();
+            (0..40)
+                .map(|frame| {
+                    if let (10, Some(model)) = (frame, model) {
+                        view.set_fling_model(model);
+                    }
+                    time += 8.0;
+                    view.step_frame(time);
+                    let step = view.get_pos_y() - last;
+                    last = view.get_pos_y();
+                    step
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let kept = fling(None);
+        let swapped = fling(Some(FlingModel::Friction { deceleration: 20000.0 }));
+        assert_eq!(kept[..10], swapped[..10]);
+
+        // the fling slows by a little more each frame, never all at once, until it brakes as
+        // the new model does on its own
+        let slowing = swapped.windows(2).map(|w| w[0] - w[1]).collect::<Vec<_>>();
+        let braking = slowing[slowing.len() - 1];
+        for (frame, w) in slowing.windows(2).enumerate().skip(8) {
+            assert!(w[1] - w[0] < braking / 8.0, "braking jumped {} to {} at {}", w[0], w[1], frame);
+        }
+        assert!(braking > 10.0 * (kept[38] - kept[39]), "braking {} after the swap", braking);
+        assert!((slowing[slowing.len() - 5] - braking).abs() < 1e-6);
+    }
+
+    #[test]
+    fn mixed_input_policy_keeps_a_backward_wheel_from_flipping_a_fling() {
+        // drags down with a pan every frame and, unless at 0.0, a wheel event just after it in
+        // the same frame; gives how far the drag moved, how much further the fling went, and
+        // how many events were dropped
+        let mixed = |policy: MixedInputPolicy, wheel: f64| {
+            let mut view = view();
+            view.set_mixed_input_policy(policy);
+            view.push_interrupt_at(100.0);
+            let mut time = 100.0;
+            let mut dropped = 0;
+            for _ in 0..10 {
+                time += 8.0;
+                view.push_pan_at(Axis::Vertical, 2.0, time - 1.0);
+                if wheel != 0.0 {
+                    view.push_wheel_at(Axis::Vertical, wheel, WheelModifier::None, time);
+                }
+                view.step_frame(time);
+                dropped += view.input_stats().dropped;
+            }
+            let dragged = view.get_pos_y();
+            view.push_fling_at(time);
+            settle(&mut view, time);
+            (dragged, view.get_pos_y() - dragged, dropped)
+        };
+
+        let (pan_dragged, pan_flung, _) = mixed(MixedInputPolicy::Merge, 0.0);
+        assert!(pan_flung > 100.0);
+
+        // merged, the wheel nudging back last in each frame is what the fling follows
+        let (dragged, flung, dropped) = mixed(MixedInputPolicy::Merge, -0.8);
+        assert!(dragged < pan_dragged && flung < 0.0, "dragged {}, flung {}", dragged, flung);
+        assert_eq!(dropped, 0);
+
+        // still moves the content, but the fling goes just as the pans would have
+        let (dragged, flung, dropped) = mixed(MixedInputPolicy::DominantVelocity, -0.8);
+        assert!(dragged < pan_dragged, "dragged {} against {}", dragged, pan_dragged);
+        assert!((flung - pan_flung).abs() < 1e-6, "flung {} against {}", flung, pan_flung);
+        assert_eq!(dropped, 0);
+
+        // the wheel never gets a say, in any frame the pan got to first
+        let (dragged, flung, dropped) = mixed(MixedInputPolicy::Separate, -0.8);
+        assert_eq!((dragged, flung), (pan_dragged, pan_flung));
+        assert_eq!(dropped, 10);
+    }
+
+
+    #[test]
+    fn cancelled_multi_touch_leaves_no_pointers_behind() {
+        // cancels a two finger drag with both fingers still down, then drags on with one,
+        // through push_pan_multi() or else push_pan(); gives how far the second drag went
+        let after_cancel = |multi: bool| {
+            let mut view = view();
+            view.push_interrupt_at(100.0);
+            let mut time = 100.0;
+            for _ in 0..5 {
+                time += 8.0;
+                view.push_pan_multi_at(1, Axis::Vertical, 10.0, time);
+                view.push_pan_multi_at(2, Axis::Vertical, 10.0, time);
+                view.step_frame(time);
+            }
+
+            let mut time = settle(&mut view, time) + 100.0;
+            let from = view.get_pos_y();
+            view.push_interrupt_at(time);
+            for _ in 0..5 {
+                time += 8.0;
+                match multi {
+                    true => view.push_pan_multi_at(3, Axis::Vertical, 10.0, time),
+                    false => view.push_pan_at(Axis::Vertical, 10.0, time),
+                };
+                view.step_frame(time);
+            }
+            view.get_pos_y() - from
+        };
+
+        assert_eq!(after_cancel(true), after_cancel(false));
+    }
+
+
+    #[test]
+    fn seed_restarts_the_random_state_without_changing_behavior() {
+        let flung = |seed: u64| {
+            let mut view = view();
+            view.set_seed(seed);
+            assert_eq!(view.seed(), seed);
+            let time = drag(&mut view, 100.0, 10.0, 8.0, 5);
+            view.push_fling_at(time);
+            settle(&mut view, time);
+            view.get_pos_y()
+        };
+        assert_eq!(flung(0), flung(12345));
+
+        let mut view = view();
+        view.set_seed(7);
+        let first = (view.rng.next_u64(), view.rng.next_u64());
+        view.set_seed(7);
+        assert_eq!((view.rng.next_u64(), view.rng.next_u64()), first);
+        view.set_seed(8);
+        assert_ne!(view.rng.next_u64(), first.0);
+    }
+
+
+    #[test]
+    fn velocity_epsilon_alone_decides_where_flings_stop() {
+        // where a fling comes to rest with the given px and px/ms epsilons
+        let flung = |epsilon: f64, velocity_epsilon: f64| {
+            let mut view = view();
+            view.set_epsilon(epsilon);
+            view.set_velocity_epsilon(velocity_epsilon);
+            let time = drag(&mut view, 100.0, 5.0, 8.0, 5);
+            view.push_fling_at(time);
+            settle(&mut view, time);
+            view.get_pos_y()
+        };
+
+        let stock = flung(0.01, 0.01);
+        assert_eq!(flung(0.0001, 0.01), stock);
+        assert!(flung(0.01, 0.1) < stock, "{} against {}", flung(0.01, 0.1), stock);
+
+        let mut view = view();
+        view.set_velocity_epsilon(0.1);
+        assert_eq!(view.current_profile().velocity_epsilon, 0.1);
     }
 }
@@ -0,0 +1,233 @@
+use std::io;
+use std::path::Path;
+
+use tini::Ini;
+
+use crate::FlingModel;
+
+const SECTION: &str = "profile";
+
+/// A complete set of the values that decide how scrolling feels, so that presets can be shared
+/// and versioned independently of any one scrollview
+///
+/// Profiles are stored as ini text (the same format as the config file) under a `[profile]`
+/// section, with keys named after the fields. Keys missing from a file keep their defaults, so
+/// a preset only needs to list what it changes.
+///
+/// A profile applied to a scrollview holds for that scrollview only, and takes precedence over
+/// the config file for the values it shares with Config
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScrollProfile {
+    /// Scale applied to pan deltas before acceleration, see set_drag_sensitivity()
+    pub drag_sensitivity: f64,
+
+    /// Velocity below which pans track 1:1, see set_accel_range()
+    pub accel_min_velocity: f64,
+    /// Cap on accelerated velocity as a multiple of the input, see set_accel_range()
+    pub accel_max_multiplier: f64,
+    pub acceleration_exponent: f64,
+    pub accel_decel_descriminant: f64,
+    pub pre_acceleration_scale_velocity: f64,
+    pub post_acceleration_scale_velocity: f64,
+
+    /// How flings decay, see set_fling_model()
+    pub fling_model: FlingModel,
+    pub fling_boost_constant_factor: f64,
+    /// Cap on fling velocity in px/ms
+    pub max_fling_velocity: f64,
+    /// See set_fling_inheritance()
+    pub fling_inheritance: bool,
+
+    pub content_mass_value: f64,
+    pub overscroll_spring_constant: f64,
+    pub bounce_damping_factor: f64,
+    pub overscroll_elasticity_coefficient: f64,
+
+    pub min_velocity_to_idle: f64,
+    /// Velocity in px/ms below which push_release() stops rather than flings
+    pub min_fling_velocity: f64,
+    pub snap_velocity_threshold: f64,
+    /// Settling precision in px, see set_epsilon()
+    pub epsilon: f64,
+    /// Speed in px/ms treated as not moving, see set_velocity_epsilon()
+    pub velocity_epsilon: f64,
+    /// Speed past which prediction is scaled back, see set_prediction_velocity_rolloff()
+    pub prediction_rolloff: f64,
+}
+
+impl Default for ScrollProfile {
+    /// The tuning a new scrollview starts out with
+    fn default() -> Self {
+        let config = crate::Config::default();
+
+        ScrollProfile {
+            drag_sensitivity: 1.0,
+
+            accel_min_velocity: 0.0,
+            accel_max_multiplier: f64::INFINITY,
+            acceleration_exponent: config.ACCELERATION_EXPONENT,
+            accel_decel_descriminant: config.ACCEL_DECEL_DESCRIMINANT,
+            pre_acceleration_scale_velocity: config.PRE_ACCEL_SCALE_VELOCITY,
+            post_acceleration_scale_velocity: config.POST_ACCEL_SCALE_VELOCITY,
+
+            fling_model: FlingModel::Curve,
+            fling_boost_constant_factor: config.FLING_BOOST_CONSTANT_FACTOR,
+            max_fling_velocity: config.MAX_FLING_VELOCITY,
+            fling_inheritance: false,
+
+            content_mass_value: config.CONTENT_MASS_VALUE,
+            overscroll_spring_constant: config.OVERSCROLL_SPRING_CONSTANT,
+            bounce_damping_factor: config.BOUNCE_DAMP_FACTOR,
+            overscroll_elasticity_coefficient: config.OVERSCROLL_ELASTICITY_COEFFICIENT,
+
+            min_velocity_to_idle: config.MIN_VELOCITY_TO_IDLE,
+            min_fling_velocity: config.MIN_FLING_VELOCITY,
+            snap_velocity_threshold: config.SNAP_VELOCITY_THRESHOLD,
+            epsilon: 0.01,
+            velocity_epsilon: 0.01,
+            prediction_rolloff: f64::INFINITY,
+        }
+    }
+}
+
+impl ScrollProfile {
+    /// Long, gliding flings that build on each other, with a soft bounce at the bounds
+    pub fn ios_like() -> ScrollProfile {
+        ScrollProfile {
+            fling_inheritance: true,
+            overscroll_spring_constant: 0.3,
+            bounce_damping_factor: 0.998,
+            ..ScrollProfile::default()
+        }
+    }
+
+    /// Flings under constant friction that stop sooner, with a stiffer bounce at the bounds
+    pub fn android_like() -> ScrollProfile {
+        ScrollProfile {
+            fling_model: FlingModel::Friction { deceleration: 4000.0 },
+            overscroll_spring_constant: 0.6,
+            bounce_damping_factor: 0.995,
+            ..ScrollProfile::default()
+        }
+    }
+
+    /// Restrained acceleration and short flings, for touchpads driving dense content
+    pub fn desktop() -> ScrollProfile {
+        ScrollProfile {
+            acceleration_exponent: 1.2,
+            accel_min_velocity: 0.5,
+            accel_max_multiplier: 4.0,
+            fling_model: FlingModel::Friction { deceleration: 8000.0 },
+            max_fling_velocity: 15.0,
+            ..ScrollProfile::default()
+        }
+    }
+
+    /// Read a profile from ini text, taking defaults for anything not given
+    pub fn from_ini(text: &str) -> ScrollProfile {
+        Self::read(&Ini::from_buffer(text))
+    }
+
+    /// Read a profile from an ini file, taking defaults for anything not given
+    pub fn from_file<P: AsRef<Path> + ?Sized>(path: &P) -> Result<ScrollProfile, io::Error> {
+        Ini::from_file(path).map(|ini| Self::read(&ini))
+    }
+
+    /// Write the profile out as ini text that from_ini() reads back unchanged
+    pub fn to_ini(&self) -> String {
+        let (model, deceleration) = match self.fling_model {
+            FlingModel::Curve => ("curve", 0.0),
+            FlingModel::Friction { deceleration } => ("friction", deceleration),
+        };
+
+        let items: Vec<(&str, String)> = vec![
+            ("drag_sensitivity", self.drag_sensitivity.to_string()),
+            ("accel_min_velocity", self.accel_min_velocity.to_string()),
+            ("accel_max_multiplier", self.accel_max_multiplier.to_string()),
+            ("acceleration_exponent", self.acceleration_exponent.to_string()),
+            ("accel_decel_descriminant", self.accel_decel_descriminant.to_string()),
+            ("pre_acceleration_scale_velocity", self.pre_acceleration_scale_velocity.to_string()),
+            ("post_acceleration_scale_velocity", self.post_acceleration_scale_velocity.to_string()),
+            ("fling_model", model.to_string()),
+            ("fling_deceleration", deceleration.to_string()),
+            ("fling_boost_constant_factor", self.fling_boost_constant_factor.to_string()),
+            ("max_fling_velocity", self.max_fling_velocity.to_string()),
+            ("fling_inheritance", self.fling_inheritance.to_string()),
+            ("content_mass_value", self.content_mass_value.to_string()),
+            ("overscroll_spring_constant", self.overscroll_spring_constant.to_string()),
+            ("bounce_damping_factor", self.bounce_damping_factor.to_string()),
+            ("overscroll_elasticity_coefficient", self.overscroll_elasticity_coefficient.to_string()),
+            ("min_velocity_to_idle", self.min_velocity_to_idle.to_string()),
+            ("min_fling_velocity", self.min_fling_velocity.to_string()),
+            ("snap_velocity_threshold", self.snap_velocity_threshold.to_string()),
+            ("epsilon", self.epsilon.to_string()),
+            ("velocity_epsilon", self.velocity_epsilon.to_string()),
+            ("prediction_rolloff", self.prediction_rolloff.to_string()),
+        ];
+
+        items.into_iter()
+            .fold(Ini::new().section(SECTION), |ini, (key, value)| ini.item(key.to_string(), value))
+            .to_buffer()
+    }
+
+    /// Overwrite the values in config that this profile gives
+    pub(crate) fn apply_to(&self, config: &mut crate::Config) {
+        config.ACCELERATION_EXPONENT = self.acceleration_exponent;
+        config.ACCEL_DECEL_DESCRIMINANT = self.accel_decel_descriminant;
+        config.PRE_ACCEL_SCALE_VELOCITY = self.pre_acceleration_scale_velocity;
+        config.POST_ACCEL_SCALE_VELOCITY = self.post_acceleration_scale_velocity;
+        config.FLING_BOOST_CONSTANT_FACTOR = self.fling_boost_constant_factor;
+        config.MAX_FLING_VELOCITY = self.max_fling_velocity;
+        config.CONTENT_MASS_VALUE = self.content_mass_value;
+        config.OVERSCROLL_SPRING_CONSTANT = self.overscroll_spring_constant;
+        config.BOUNCE_DAMP_FACTOR = self.bounce_damping_factor;
+        config.OVERSCROLL_ELASTICITY_COEFFICIENT = self.overscroll_elasticity_coefficient;
+        config.MIN_VELOCITY_TO_IDLE = self.min_velocity_to_idle;
+        config.MIN_FLING_VELOCITY = self.min_fling_velocity;
+        config.SNAP_VELOCITY_THRESHOLD = self.snap_velocity_threshold;
+    }
+
+    fn read(ini: &Ini) -> ScrollProfile {
+        let mut p = ScrollProfile::default();
+
+        let get = |key: &str, into: &mut f64| {
+            if let Some(v) = ini.get(SECTION, key) {
+                *into = v;
+            }
+        };
+
+        get("drag_sensitivity", &mut p.drag_sensitivity);
+        get("accel_min_velocity", &mut p.accel_min_velocity);
+        get("accel_max_multiplier", &mut p.accel_max_multiplier);
+        get("acceleration_exponent", &mut p.acceleration_exponent);
+        get("accel_decel_descriminant", &mut p.accel_decel_descriminant);
+        get("pre_acceleration_scale_velocity", &mut p.pre_acceleration_scale_velocity);
+        get("post_acceleration_scale_velocity", &mut p.post_acceleration_scale_velocity);
+        get("fling_boost_constant_factor", &mut p.fling_boost_constant_factor);
+        get("max_fling_velocity", &mut p.max_fling_velocity);
+        get("content_mass_value", &mut p.content_mass_value);
+        get("overscroll_spring_constant", &mut p.overscroll_spring_constant);
+        get("bounce_damping_factor", &mut p.bounce_damping_factor);
+        get("overscroll_elasticity_coefficient", &mut p.overscroll_elasticity_coefficient);
+        get("min_velocity_to_idle", &mut p.min_velocity_to_idle);
+        get("min_fling_velocity", &mut p.min_fling_velocity);
+        get("snap_velocity_threshold", &mut p.snap_velocity_threshold);
+        get("epsilon", &mut p.epsilon);
+        get("velocity_epsilon", &mut p.velocity_epsilon);
+        get("prediction_rolloff", &mut p.prediction_rolloff);
+
+        if let Some(v) = ini.get(SECTION, "fling_inheritance") {
+            p.fling_inheritance = v;
+        }
+
+        // friction is meaningless without a deceleration, so it needs both keys to take effect
+        match (ini.get::<String>(SECTION, "fling_model").as_deref(), ini.get(SECTION, "fling_deceleration")) {
+            (Some("curve"), _) => p.fling_model = FlingModel::Curve,
+            (Some("friction"), Some(deceleration)) => p.fling_model = FlingModel::Friction { deceleration },
+            _ => {}
+        }
+
+        p
+    }
+}
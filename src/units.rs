@@ -0,0 +1,27 @@
+//! Typed lengths, for callers that want the compiler to keep physical and device independent
+//! pixels apart
+//!
+//! Scrollviews work in physical pixels internally. Lengths in device independent pixels are
+//! converted using the scale factor given to Scrollview::set_scale_factor()
+
+/// A length in physical pixels
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Px(pub f64);
+
+/// A length in device independent pixels, which are scale factor physical pixels each
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Dip(pub f64);
+
+/// A length that can be handed to a scrollview, whatever its units
+pub trait Length: Copy {
+    /// The length in physical pixels, given the number of physical pixels per dip
+    fn as_px(&self, scale_factor: f64) -> f64;
+}
+
+impl Length for Px {
+    fn as_px(&self, _: f64) -> f64 { self.0 }
+}
+
+impl Length for Dip {
+    fn as_px(&self, scale_factor: f64) -> f64 { self.0 * scale_factor }
+}
@@ -0,0 +1,101 @@
+//! Checks for misuse of the scrollview api, such as sampling before any geometry has been
+//! given or pushing events out of order
+//!
+//! Only compiled in with debug assertions or the `strict` feature. Misuse is logged to stderr,
+//! or panics under `strict`. In release builds without `strict` this is a zero sized no-op
+
+/// How much event time may pile up without a step_frame() in between before it's reported
+#[cfg(any(debug_assertions, feature = "strict"))]
+const MAX_UNSAMPLED_SPAN_MS: f64 = 1000.0;
+
+#[cfg(any(debug_assertions, feature = "strict"))]
+#[derive(Default)]
+pub struct Validator {
+    geometry_set: bool,
+    last_event_time: Option<f64>,
+    first_unsampled_time: Option<f64>,
+}
+
+#[cfg(any(debug_assertions, feature = "strict"))]
+impl Validator {
+    pub fn new() -> Validator {
+        Validator::default()
+    }
+
+    pub fn geometry_set(&mut self) {
+        self.geometry_set = true;
+    }
+
+    /// Called from anything that reports position, before doing so
+    pub fn queried(&self, by: &str) {
+        if !self.geometry_set {
+            Self::misuse(&format!("{} called before set_geometry()", by));
+        }
+    }
+
+    pub fn sampled(&mut self) {
+        self.queried("step_frame()");
+        self.first_unsampled_time = None;
+    }
+
+    /// Called with the timestamp (in millis) of every event pushed
+    pub fn event(&mut self, time: f64, by: &str) {
+        if let Some(last) = self.last_event_time {
+            if time < last {
+                Self::misuse(&format!("{} given timestamp {} after a later one, {}", by, time, last));
+            }
+        }
+        self.last_event_time = Some(time);
+
+        let first = *self.first_unsampled_time.get_or_insert(time);
+        if time - first > MAX_UNSAMPLED_SPAN_MS {
+            Self::misuse(&format!("events spanning over {}ms pushed without a step_frame() in between", MAX_UNSAMPLED_SPAN_MS));
+            // only report once per stretch without sampling
+            self.first_unsampled_time = Some(time);
+        }
+    }
+
+    /// Called with the values of any input dropped for being NaN or infinite
+    pub fn non_finite(by: &str, values: &[f64]) {
+        Self::misuse(&format!("non-finite input to {} ignored: {:?}", by, values));
+    }
+
+    /// Called when a track's own state has gone non-finite and been reset to rest
+    pub fn recovered() {
+        Self::misuse("track state went non-finite and was reset to rest");
+    }
+
+    fn misuse(message: &str) {
+        if cfg!(feature = "strict") {
+            panic!("libscroll misuse: {}", message);
+        } else {
+            eprintln!("libscroll misuse: {}", message);
+        }
+    }
+}
+
+#[cfg(not(any(debug_assertions, feature = "strict")))]
+pub struct Validator;
+
+#[cfg(not(any(debug_assertions, feature = "strict")))]
+impl Validator {
+    pub fn new() -> Validator { Validator }
+
+    #[inline]
+    pub fn geometry_set(&mut self) {}
+
+    #[inline]
+    pub fn queried(&self, _by: &str) {}
+
+    #[inline]
+    pub fn sampled(&mut self) {}
+
+    #[inline]
+    pub fn event(&mut self, _time: f64, _by: &str) {}
+
+    #[inline]
+    pub fn non_finite(_by: &str, _values: &[f64]) {}
+
+    #[inline]
+    pub fn recovered() {}
+}
@@ -0,0 +1,162 @@
+//! Caller-replaceable estimation of release velocity, see Scrollview::set_velocity_tracker()
+
+use std::collections::VecDeque;
+
+use crate::Axis;
+
+// how far back from the newest sample on an axis samples are kept, and how many at most
+const DEFAULT_HORIZON_MS: f64 = 100.0;
+const MAX_SAMPLES: usize = 20;
+
+/// Estimates how fast a pan was moving when it was let go, to start the fling from
+///
+/// Fed every pan delta (after input filters and drag sensitivity) with the time it was pushed
+/// at, and cleared at each interrupt. Deltas are changes in position, whatever
+/// set_pan_direction() says they were pushed as
+pub trait VelocityTracker: Send {
+    fn add_sample(&mut self, axis: Axis, time: f64, delta: f64);
+
+    /// Velocity in px/ms on axis as of the newest sample, 0 if there's too little to go on
+    fn estimate(&self, axis: Axis) -> f64;
+
+    fn clear(&mut self);
+}
+
+/// Samples for one axis, as time and cumulative position, limited to a horizon back from the
+/// newest. Deltas sharing a timestamp are merged into one sample
+#[derive(Clone, Debug, Default)]
+struct History {
+    samples: VecDeque<(f64, f64)>,
+}
+
+impl History {
+    fn push(&mut self, time: f64, delta: f64, horizon: f64) {
+        let position = self.samples.back().map_or(0.0, |&(_, position)| position) + delta;
+
+        match self.samples.back_mut() {
+            Some(last) if time <= last.0 => last.1 = position,
+            _ => self.samples.push_back((time, position)),
+        }
+
+        while self.samples.len() > MAX_SAMPLES
+            || self.samples.front().is_some_and(|&(first, _)| time - first > horizon)
+        {
+            self.samples.pop_front();
+        }
+    }
+}
+
+/// Averages the velocity between each pair of consecutive samples, weighting newer pairs more
+/// heavily so the estimate follows a pan that's speeding up or slowing down
+#[derive(Clone, Debug)]
+pub struct WeightedAverageTracker {
+    horizon: f64,
+    x: History,
+    y: History,
+}
+
+impl WeightedAverageTracker {
+    pub fn new() -> WeightedAverageTracker {
+        WeightedAverageTracker::with_horizon(DEFAULT_HORIZON_MS)
+    }
+
+    /// Only consider samples up to horizon_ms older than the newest. Defaults to 100
+    pub fn with_horizon(horizon_ms: f64) -> WeightedAverageTracker {
+        WeightedAverageTracker { horizon: horizon_ms.max(0.0), x: History::default(), y: History::default() }
+    }
+}
+
+impl Default for WeightedAverageTracker {
+    fn default() -> Self { WeightedAverageTracker::new() }
+}
+
+impl VelocityTracker for WeightedAverageTracker {
+    fn add_sample(&mut self, axis: Axis, time: f64, delta: f64) {
+        match axis {
+            Axis::Horizontal => self.x.push(time, delta, self.horizon),
+            Axis::Vertical => self.y.push(time, delta, self.horizon),
+        }
+    }
+
+    fn estimate(&self, axis: Axis) -> f64 {
+        let history = match axis {
+            Axis::Horizontal => &self.x,
+            Axis::Vertical => &self.y,
+        };
+
+        let samples: Vec<&(f64, f64)> = history.samples.iter().collect();
+        let (total, weights) = samples.windows(2).enumerate().fold((0.0, 0.0), |(total, weights), (i, pair)| {
+            let (&(t0, p0), &(t1, p1)) = (pair[0], pair[1]);
+            let weight = (i + 1) as f64;
+
+            (total + weight * (p1 - p0) / (t1 - t0), weights + weight)
+        });
+
+        if weights > 0.0 { total / weights } else { 0.0 }
+    }
+
+    fn clear(&mut self) {
+        self.x.samples.clear();
+        self.y.samples.clear();
+    }
+}
+
+/// Fits a straight line through the position of every sample by least squares and takes its
+/// slope, which shrugs off jitter in individual deltas or timestamps better than averaging
+#[derive(Clone, Debug)]
+pub struct LeastSquaresTracker {
+    horizon: f64,
+    x: History,
+    y: History,
+}
+
+impl LeastSquaresTracker {
+    pub fn new() -> LeastSquaresTracker {
+        LeastSquaresTracker::with_horizon(DEFAULT_HORIZON_MS)
+    }
+
+    /// Only consider samples up to horizon_ms older than the newest. Defaults to 100
+    pub fn with_horizon(horizon_ms: f64) -> LeastSquaresTracker {
+        LeastSquaresTracker { horizon: horizon_ms.max(0.0), x: History::default(), y: History::default() }
+    }
+}
+
+impl Default for LeastSquaresTracker {
+    fn default() -> Self { LeastSquaresTracker::new() }
+}
+
+impl VelocityTracker for LeastSquaresTracker {
+    fn add_sample(&mut self, axis: Axis, time: f64, delta: f64) {
+        match axis {
+            Axis::Horizontal => self.x.push(time, delta, self.horizon),
+            Axis::Vertical => self.y.push(time, delta, self.horizon),
+        }
+    }
+
+    fn estimate(&self, axis: Axis) -> f64 {
+        let samples = match axis {
+            Axis::Horizontal => &self.x.samples,
+            Axis::Vertical => &self.y.samples,
+        };
+
+        if samples.len() < 2 {
+            return 0.0;
+        }
+
+        let n = samples.len() as f64;
+        let mean_time = samples.iter().map(|&(t, _)| t).sum::<f64>() / n;
+        let mean_position = samples.iter().map(|&(_, p)| p).sum::<f64>() / n;
+
+        let (covariance, variance) = samples.iter().fold((0.0, 0.0), |(covariance, variance), &(t, p)| {
+            let dt = t - mean_time;
+            (covariance + dt * (p - mean_position), variance + dt * dt)
+        });
+
+        if variance > 0.0 { covariance / variance } else { 0.0 }
+    }
+
+    fn clear(&mut self) {
+        self.x.samples.clear();
+        self.y.samples.clear();
+    }
+}